@@ -0,0 +1,8 @@
+-- 0005_add_component_stress_data.sql
+-- Adds per-component electrical stress data used by the IEC 61709 / SN 29500 stress factors
+-- voltage_ratio: applied-to-rated voltage (U/Urat), drives πU
+-- current_ratio: applied-to-rated current (I/Irat), drives πI
+
+ALTER TABLE components ADD COLUMN IF NOT EXISTS capacitor_type TEXT;
+ALTER TABLE components ADD COLUMN IF NOT EXISTS voltage_ratio DOUBLE PRECISION CHECK (voltage_ratio >= 0);
+ALTER TABLE components ADD COLUMN IF NOT EXISTS current_ratio DOUBLE PRECISION CHECK (current_ratio >= 0);
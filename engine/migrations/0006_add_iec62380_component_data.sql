@@ -0,0 +1,19 @@
+-- 0006_add_iec62380_component_data.sql
+-- Adds the component data required by the IEC TR 62380 die + package + EOS model
+-- technology:         die technology (e.g. 'microcontroller', 'sram', 'flash', 'analog', 'mixed')
+-- transistor_count:   N, number of transistors on the die
+-- manufacture_year:   year of manufacture (a = year − 1998)
+-- package_type:       package family (e.g. 'so', 'tssop', 'qfp', 'qfn', 'bga', 'dip')
+-- pin_count:          S, number of package pins
+-- power_dissipation:  dissipated power P (W)
+-- thermal_resistance: junction-to-ambient Rth (K/W), Tj = Ta + Rth × P
+-- interface_category: EOS category for interface circuits (NULL / 'none' for non-interface)
+
+ALTER TABLE components ADD COLUMN IF NOT EXISTS technology TEXT;
+ALTER TABLE components ADD COLUMN IF NOT EXISTS transistor_count BIGINT CHECK (transistor_count >= 0);
+ALTER TABLE components ADD COLUMN IF NOT EXISTS manufacture_year INT;
+ALTER TABLE components ADD COLUMN IF NOT EXISTS package_type TEXT;
+ALTER TABLE components ADD COLUMN IF NOT EXISTS pin_count INT CHECK (pin_count > 0);
+ALTER TABLE components ADD COLUMN IF NOT EXISTS power_dissipation DOUBLE PRECISION CHECK (power_dissipation >= 0);
+ALTER TABLE components ADD COLUMN IF NOT EXISTS thermal_resistance DOUBLE PRECISION CHECK (thermal_resistance >= 0);
+ALTER TABLE components ADD COLUMN IF NOT EXISTS interface_category TEXT;
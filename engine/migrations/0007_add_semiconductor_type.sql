@@ -0,0 +1,8 @@
+-- 0007_add_semiconductor_type.sql
+-- Adds the discrete semiconductor type used to select the SN 29500-3 model
+-- Diodes:       'signal', 'rectifier', 'zener', 'tvs'
+-- Transistors:  'bjt', 'mosfet'
+-- voltage_ratio / current_ratio (migration 0005) drive πU and πI,
+-- power_dissipation / thermal_resistance (migration 0006) drive the junction temperature
+
+ALTER TABLE components ADD COLUMN IF NOT EXISTS semiconductor_type TEXT;
@@ -0,0 +1,5 @@
+-- 0008_add_switching_rate.sql
+-- Adds the operating rate of relays and switches (operations per hour),
+-- used by the SN 29500 switching-rate factor πW
+
+ALTER TABLE components ADD COLUMN IF NOT EXISTS switching_rate DOUBLE PRECISION CHECK (switching_rate >= 0);
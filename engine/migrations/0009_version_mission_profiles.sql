@@ -0,0 +1,44 @@
+-- 0009_version_mission_profiles.sql
+-- Rewrites temp_tau_profile into the versioned segment schema (version 2):
+--   {"version": 2, "segments": [{"temperature", "tau", "humidity", "delta_t", "cycles_per_year", "on"}]}
+-- The engine still upgrades legacy layouts on load; this keeps stored data uniform.
+
+BEGIN;
+
+-- Legacy layout: [{temp, tau}]
+UPDATE mission_profiles
+SET temp_tau_profile = jsonb_build_object(
+    'version', 2,
+    'segments', COALESCE((
+        SELECT jsonb_agg(jsonb_build_object('temperature', e->'temp', 'tau', e->'tau'))
+        FROM jsonb_array_elements(temp_tau_profile) AS e
+    ), '[]'::jsonb)
+)
+WHERE jsonb_typeof(temp_tau_profile) = 'array';
+
+-- Legacy layout: {temperature: [...], tau: [...]}
+UPDATE mission_profiles
+SET temp_tau_profile = jsonb_build_object(
+    'version', 2,
+    'segments', COALESCE((
+        SELECT jsonb_agg(jsonb_build_object(
+            'temperature', temp_tau_profile->'temperature'->i,
+            'tau', temp_tau_profile->'tau'->i
+        ))
+        FROM generate_series(0, jsonb_array_length(temp_tau_profile->'temperature') - 1) AS i
+    ), '[]'::jsonb)
+)
+WHERE jsonb_typeof(temp_tau_profile) = 'object'
+  AND jsonb_typeof(temp_tau_profile->'temperature') = 'array';
+
+-- Legacy layout: {segments: [...]} without a version
+UPDATE mission_profiles
+SET temp_tau_profile = temp_tau_profile || '{"version": 2}'::jsonb
+WHERE jsonb_typeof(temp_tau_profile) = 'object'
+  AND temp_tau_profile ? 'segments'
+  AND NOT temp_tau_profile ? 'version';
+
+COMMENT ON COLUMN mission_profiles.temp_tau_profile IS
+    'Versioned mission profile: {"version": 2, "segments": [{temperature, tau, humidity, delta_t, cycles_per_year, on}]}';
+
+COMMIT;
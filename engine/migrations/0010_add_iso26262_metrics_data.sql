@@ -0,0 +1,13 @@
+-- 0010_add_iso26262_metrics_data.sql
+-- Adds the data needed for the ISO 26262-5 hardware architectural metrics (SPFM / LFM)
+-- projects.asil_level:           ASIL of the project ('QM', 'A', 'B', 'C', 'D')
+-- failure_modes.safe_fraction:   share of the mode that cannot violate the safety goal (0–1)
+-- failure_modes.violates_goal:   mode can violate the safety goal in absence of a safety mechanism
+-- failure_modes.latent_coverage: diagnostic coverage for latent multiple-point faults (0–1)
+-- failure_modes.detection_coverage (0001) is the coverage for single-point / residual faults
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS asil_level TEXT;
+
+ALTER TABLE failure_modes ADD COLUMN IF NOT EXISTS safe_fraction REAL CHECK (safe_fraction BETWEEN 0 AND 1);
+ALTER TABLE failure_modes ADD COLUMN IF NOT EXISTS violates_goal BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE failure_modes ADD COLUMN IF NOT EXISTS latent_coverage REAL CHECK (latent_coverage BETWEEN 0 AND 1);
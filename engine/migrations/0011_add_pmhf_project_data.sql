@@ -0,0 +1,7 @@
+-- 0011_add_pmhf_project_data.sql
+-- Adds the time parameters of the ISO 26262-5 PMHF evaluation
+-- projects.vehicle_lifetime_hours:       operating lifetime of the vehicle (h)
+-- projects.mpf_detection_interval_hours: multiple-point fault detection interval, e.g. one driving cycle (h)
+
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS vehicle_lifetime_hours DOUBLE PRECISION CHECK (vehicle_lifetime_hours > 0);
+ALTER TABLE projects ADD COLUMN IF NOT EXISTS mpf_detection_interval_hours DOUBLE PRECISION CHECK (mpf_detection_interval_hours > 0);
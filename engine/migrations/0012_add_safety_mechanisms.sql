@@ -0,0 +1,26 @@
+-- 0012_add_safety_mechanisms.sql
+-- Safety mechanisms with claimed diagnostic coverage, assignable to many failure modes.
+-- The FMEDA takes detection / latent coverage from the assigned mechanisms, so changing
+-- a mechanism's DC updates every failure mode it is assigned to.
+
+CREATE TABLE IF NOT EXISTS safety_mechanisms (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name TEXT NOT NULL UNIQUE,
+  description TEXT,
+  dc_permanent REAL NOT NULL CHECK (dc_permanent BETWEEN 0 AND 1),   -- claimed DC for permanent faults
+  dc_transient REAL CHECK (dc_transient BETWEEN 0 AND 1),            -- claimed DC for transient faults
+  annex_d_reference TEXT,                                            -- ISO 26262-5 Annex D technique, e.g. 'D.2.4.1'
+  test_interval_hours DOUBLE PRECISION CHECK (test_interval_hours > 0),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- covers_latent: mechanism detects latent multiple-point faults (latent coverage)
+-- instead of single-point / residual faults (detection coverage)
+CREATE TABLE IF NOT EXISTS failure_mode_safety_mechanisms (
+  failure_mode_id UUID NOT NULL REFERENCES failure_modes(id) ON DELETE CASCADE,
+  safety_mechanism_id UUID NOT NULL REFERENCES safety_mechanisms(id) ON DELETE CASCADE,
+  covers_latent BOOLEAN NOT NULL DEFAULT FALSE,
+  PRIMARY KEY (failure_mode_id, safety_mechanism_id, covers_latent)
+);
+
+CREATE INDEX IF NOT EXISTS idx_fm_safety_mechanisms_mechanism ON failure_mode_safety_mechanisms(safety_mechanism_id);
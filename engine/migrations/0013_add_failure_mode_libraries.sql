@@ -0,0 +1,77 @@
+-- 0013_add_failure_mode_libraries.sql
+-- Versioned failure-mode distribution libraries.
+-- Each entry applies to exactly one selector: an MPN, a component family
+-- (resistor / capacitor / semiconductor type or IC technology) or a component type.
+-- The shares of every selector must sum to 1 within a library version.
+--
+-- Scope: the IEC 61709, SN 29500 and ISO 26262-11 distribution tables are
+-- licensed data and are not shipped. Load them as their own library names and
+-- versions from the licensed sources; only the illustrative 'generic' library
+-- below is seeded.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS failure_mode_libraries (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  name TEXT NOT NULL,
+  version INT NOT NULL CHECK (version > 0),
+  source TEXT,                -- e.g. 'IEC 61709 Annex', 'SN 29500-1', 'ISO 26262-11'
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (name, version)
+);
+
+CREATE TABLE IF NOT EXISTS failure_mode_library_entries (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  library_id UUID NOT NULL REFERENCES failure_mode_libraries(id) ON DELETE CASCADE,
+  mpn TEXT,
+  family TEXT,
+  component_type TEXT,
+  mode TEXT NOT NULL,
+  share REAL NOT NULL CHECK (share > 0 AND share <= 1),
+  CHECK (num_nonnulls(mpn, family, component_type) = 1),
+  UNIQUE (library_id, mpn, family, component_type, mode)
+);
+
+CREATE INDEX IF NOT EXISTS idx_fm_library_entries_library ON failure_mode_library_entries(library_id);
+
+-- Illustrative distributions, not taken from a standard
+INSERT INTO failure_mode_libraries (name, version, source, description)
+VALUES ('generic', 1, 'illustrative', 'Illustrative failure-mode distributions per component type and family; not taken from a standard')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO failure_mode_library_entries (library_id, mpn, family, component_type, mode, share)
+SELECT l.id, NULL, e.family, e.component_type, e.mode, e.share
+FROM failure_mode_libraries l,
+(VALUES
+  (NULL, 'resistor', 'open', 0.6),
+  (NULL, 'resistor', 'short', 0.1),
+  (NULL, 'resistor', 'drift', 0.3),
+  (NULL, 'capacitor', 'short', 0.4),
+  (NULL, 'capacitor', 'open', 0.3),
+  (NULL, 'capacitor', 'drift', 0.3),
+  ('ceramic class 2', NULL, 'short', 0.7),
+  ('ceramic class 2', NULL, 'open', 0.1),
+  ('ceramic class 2', NULL, 'drift', 0.2),
+  ('aluminium', NULL, 'open', 0.4),
+  ('aluminium', NULL, 'short', 0.3),
+  ('aluminium', NULL, 'drift', 0.3),
+  (NULL, 'diode', 'short', 0.5),
+  (NULL, 'diode', 'open', 0.3),
+  (NULL, 'diode', 'drift', 0.2),
+  (NULL, 'transistor', 'short', 0.4),
+  (NULL, 'transistor', 'open', 0.4),
+  (NULL, 'transistor', 'drift', 0.2),
+  (NULL, 'ic', 'output stuck', 0.4),
+  (NULL, 'ic', 'incorrect output', 0.4),
+  (NULL, 'ic', 'drift', 0.2),
+  (NULL, 'inductor', 'open', 0.4),
+  (NULL, 'inductor', 'short', 0.2),
+  (NULL, 'inductor', 'drift', 0.4),
+  (NULL, 'connector', 'open', 0.8),
+  (NULL, 'connector', 'short', 0.2)
+) AS e(family, component_type, mode, share)
+WHERE l.name = 'generic' AND l.version = 1
+ON CONFLICT DO NOTHING;
+
+COMMIT;
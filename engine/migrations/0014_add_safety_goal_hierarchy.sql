@@ -0,0 +1,70 @@
+-- 0014_add_safety_goal_hierarchy.sql
+-- Safety goals and the hardware element hierarchy of a project:
+-- Project → System → HardwareBlock (nested) → Component
+-- FunctionalBlocks are realised by HardwareBlocks; failure modes of a block
+-- are mapped to the safety goals they violate, each goal with its own ASIL.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS safety_goals (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  description TEXT,
+  asil_level TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (project_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS systems (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (project_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS hardware_blocks (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+  parent_id UUID REFERENCES hardware_blocks(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS functional_blocks (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  system_id UUID NOT NULL REFERENCES systems(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  description TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Hardware blocks realising a functional block
+CREATE TABLE IF NOT EXISTS functional_block_hardware (
+  functional_block_id UUID NOT NULL REFERENCES functional_blocks(id) ON DELETE CASCADE,
+  hardware_block_id UUID NOT NULL REFERENCES hardware_blocks(id) ON DELETE CASCADE,
+  PRIMARY KEY (functional_block_id, hardware_block_id)
+);
+
+ALTER TABLE components
+ADD COLUMN IF NOT EXISTS hardware_block_id UUID REFERENCES hardware_blocks(id) ON DELETE SET NULL;
+
+-- Failure modes of a hardware block that affect a safety goal.
+-- violates_goal: directly violates the goal (single-point / residual) rather than
+-- contributing only as a multiple-point fault.
+CREATE TABLE IF NOT EXISTS block_failure_mode_goals (
+  hardware_block_id UUID NOT NULL REFERENCES hardware_blocks(id) ON DELETE CASCADE,
+  failure_mode_id UUID NOT NULL REFERENCES failure_modes(id) ON DELETE CASCADE,
+  safety_goal_id UUID NOT NULL REFERENCES safety_goals(id) ON DELETE CASCADE,
+  violates_goal BOOLEAN NOT NULL DEFAULT TRUE,
+  PRIMARY KEY (hardware_block_id, failure_mode_id, safety_goal_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_components_hardware_block ON components(hardware_block_id);
+CREATE INDEX IF NOT EXISTS idx_hardware_blocks_system ON hardware_blocks(system_id);
+CREATE INDEX IF NOT EXISTS idx_block_fm_goals_goal ON block_failure_mode_goals(safety_goal_id);
+
+COMMIT;
@@ -0,0 +1,19 @@
+-- 0015_add_fault_trees.sql
+-- Fault trees of a project. The tree (gates and basic events) is stored as JSON;
+-- basic events reference FMEDA failure modes so the FTA reuses the FMEDA FIT.
+-- {
+--   "top": "G1",
+--   "gates":  [{ "id": "G1", "type": "or", "inputs": ["E1", "G2"] },
+--              { "id": "G2", "type": "k_of_n", "k": 2, "inputs": ["E2", "E3", "E4"] }],
+--   "events": [{ "id": "E1", "component_id": "...", "failure_mode_id": "..." },
+--              { "id": "C1", "probability": 0.01 }]
+-- }
+
+CREATE TABLE IF NOT EXISTS fault_trees (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  tree JSONB NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (project_id, name)
+);
@@ -0,0 +1,17 @@
+-- 0016_add_reliability_block_diagrams.sql
+-- Reliability block diagrams of a project, stored as a JSON block tree;
+-- units reference components so the RBD reuses the FMEDA FIT.
+-- { "type": "series", "blocks": [
+--     { "type": "parallel", "blocks": [ { "type": "unit", "component_id": "..." },
+--                                       { "type": "unit", "component_id": "..." } ] },
+--     { "type": "k_out_of_n", "k": 2, "blocks": [ ... ] },
+--     { "type": "standby", "switch_reliability": 0.99, "blocks": [ ... ] } ] }
+
+CREATE TABLE IF NOT EXISTS reliability_block_diagrams (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+  name TEXT NOT NULL,
+  diagram JSONB NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+  UNIQUE (project_id, name)
+);
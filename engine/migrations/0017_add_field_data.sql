@@ -0,0 +1,25 @@
+-- 0017_add_field_data.sql
+-- Field return data per component variant and the field-justified FIT derived from it.
+-- component_variants.field_fit keeps the adopted field FIT next to the handbook ref_fit;
+-- field_data_id traces it back to the data it was derived from.
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS field_data (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  variant_id UUID NOT NULL REFERENCES component_variants(id) ON DELETE CASCADE,
+  source TEXT NOT NULL,                                            -- warranty database, 8D report, customer, ...
+  failures INT NOT NULL CHECK (failures >= 0),
+  operating_hours DOUBLE PRECISION NOT NULL CHECK (operating_hours > 0),  -- cumulative fleet hours
+  period_start TIMESTAMPTZ,
+  period_end TIMESTAMPTZ,
+  notes TEXT,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_field_data_variant ON field_data(variant_id);
+
+ALTER TABLE component_variants ADD COLUMN IF NOT EXISTS field_fit DOUBLE PRECISION CHECK (field_fit >= 0);
+ALTER TABLE component_variants ADD COLUMN IF NOT EXISTS field_data_id UUID REFERENCES field_data(id) ON DELETE SET NULL;
+
+COMMIT;
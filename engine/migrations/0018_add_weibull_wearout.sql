@@ -0,0 +1,18 @@
+-- 0018_add_weibull_wearout.sql
+-- Optional Weibull wear-out of a component variant (electrolytic capacitors, relays, fans, ...)
+-- R(t) = exp[-(t/η)^β]; both parameters are set together or not at all
+
+ALTER TABLE component_variants ADD COLUMN IF NOT EXISTS weibull_beta DOUBLE PRECISION CHECK (weibull_beta > 0);
+ALTER TABLE component_variants ADD COLUMN IF NOT EXISTS weibull_eta_hours DOUBLE PRECISION CHECK (weibull_eta_hours > 0);
+
+DO $$
+BEGIN
+  IF NOT EXISTS (
+    SELECT 1 FROM pg_constraint
+    WHERE conname = 'component_variants_weibull_pair'
+      AND conrelid = 'component_variants'::regclass
+  ) THEN
+    ALTER TABLE component_variants ADD CONSTRAINT component_variants_weibull_pair
+      CHECK ((weibull_beta IS NULL) = (weibull_eta_hours IS NULL));
+  END IF;
+END $$;
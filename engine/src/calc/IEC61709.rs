@@ -0,0 +1,205 @@
+use crate::errors::FmedaError;
+use crate::models::ComponentVariant;
+use super::factors::{
+    capacitor_model, ic_model, junction_temperature_rise, part_model, PiTModel, StressModel,
+    I_REF_RATIO, RESISTOR_PIT, THETA_REF, THETA_REF_JUNCTION, U_REF_RATIO,
+};
+use super::standard::{CalcContext, ReliabilityStandard};
+
+/// Stress models that apply to one IEC 61709 component family.
+/// Semiconductors take πT at the virtual junction temperature θj = θamb + Rth × P,
+/// with the reference FIT stated at a 55 °C junction by default.
+struct FamilyModel {
+    pi_t: PiTModel,
+    /// Default temperature θ1 of the reference FIT (°C)
+    theta_ref: f64,
+    /// Rise of the πT temperature over the segment ambient (K)
+    temp_rise: f64,
+    pi_u: Option<StressModel>,
+    pi_i: Option<StressModel>,
+}
+
+impl FamilyModel {
+    const fn thermal(pi_t: PiTModel) -> Self {
+        Self { pi_t, theta_ref: THETA_REF, temp_rise: 0.0, pi_u: None, pi_i: None }
+    }
+
+    const fn with_voltage(pi_t: PiTModel, c2: f64, c3: f64) -> Self {
+        Self { pi_u: Some(StressModel::new(c2, c3, U_REF_RATIO)), ..Self::thermal(pi_t) }
+    }
+
+    const fn with_current(pi_t: PiTModel, c2: f64, c3: f64) -> Self {
+        Self { pi_i: Some(StressModel::new(c2, c3, I_REF_RATIO)), ..Self::thermal(pi_t) }
+    }
+
+    /// πT at the virtual junction rather than the ambient
+    fn at_junction(self, ctx: &CalcContext) -> Self {
+        Self { theta_ref: THETA_REF_JUNCTION, temp_rise: junction_temperature_rise(&ctx.stress), ..self }
+    }
+}
+
+/// IEC 61709 reference-conditions standard
+pub struct Iec61709;
+
+impl ReliabilityStandard for Iec61709 {
+    fn name(&self) -> &str {
+        "IEC61709"
+    }
+
+    fn calc_fit(&self, ctx: &CalcContext) -> Result<f64, FmedaError> {
+        // IEC61709 scales the variant reference FIT to the mission profile
+        let variant = ctx
+            .variant
+            .ok_or_else(|| FmedaError::MissingVariant(self.name().to_string()))?;
+        Ok(calc_fit(ctx, variant))
+    }
+
+    fn segment_pi_t(&self, ctx: &CalcContext) -> Option<Vec<f64>> {
+        let variant = ctx.variant?;
+        let model = family_model(ctx)?;
+        let pi_t = model.pi_t.scaled(ctx.ea_scale);
+        let theta_ref_fit = variant.ref_temp.unwrap_or(model.theta_ref);
+        Some(
+            ctx.profile
+                .segments()
+                .iter()
+                .map(|s| pi_t.pi_t(theta_ref_fit, s.temperature + model.temp_rise))
+                .collect(),
+        )
+    }
+}
+
+/// Entry point: calculate FIT from the variant reference FIT under IEC 61709 stress factors
+pub fn calc_fit(ctx: &CalcContext, variant: &ComponentVariant) -> f64 {
+    let comp = ctx.component;
+    let ref_fit = variant.reference_fit();
+
+    let Some(model) = family_model(ctx) else {
+        report!(
+            "[WARN] No IEC 61709 model for component type '{}' → returning ref_fit = {}",
+            comp.component_type, ref_fit
+        );
+        return ref_fit;
+    };
+
+    let theta_ref_fit = variant.ref_temp.unwrap_or(model.theta_ref);
+    let segments: Vec<(f64, f64)> = ctx
+        .profile
+        .temp_tau_profile
+        .temp_tau_pairs()
+        .into_iter()
+        .map(|(temperature, tau)| (temperature + model.temp_rise, tau))
+        .collect();
+    let Some(pi_t) = model.pi_t.scaled(ctx.ea_scale).weighted(&segments, theta_ref_fit) else {
+        report!("[WARN] No usable temperature–τ segments found → returning ref_fit = {}", ref_fit);
+        return ref_fit;
+    };
+
+    let pi_u = model.pi_u.map_or(1.0, |m| m.factor(ctx.stress.voltage_ratio));
+    let pi_i = model.pi_i.map_or(1.0, |m| m.factor(ctx.stress.current_ratio));
+
+    let fit = ref_fit * pi_t * pi_u * pi_i;
+    report!(
+        "[IEC61709] {} | ref_fit = {:.6} | ΔT = {:.2} K | ΣWeighted(πT) = {:.6} | πU = {:.6} | πI = {:.6} → FIT = {:.6}",
+        comp.manufacturer_part_number, ref_fit, model.temp_rise, pi_t, pi_u, pi_i, fit
+    );
+
+    fit
+}
+
+/// Select the IEC 61709 stress model for the component family. Resistor,
+/// capacitor, IC and part tables are shared with SN 29500 (see `factors`).
+fn family_model(ctx: &CalcContext) -> Option<FamilyModel> {
+    let comp = ctx.component;
+    let comp_type = comp.component_type.to_lowercase();
+    let model = match comp_type.as_str() {
+        "resistor" => match comp.resistor_type.as_deref().map(str::to_lowercase).as_deref() {
+            Some("wirewound") => FamilyModel::thermal(PiTModel::single(0.16, THETA_REF)),
+            _ => FamilyModel::thermal(RESISTOR_PIT),
+        },
+        "capacitor" => {
+            let (pi_t, pi_u) = capacitor_model(comp.capacitor_type.as_deref());
+            FamilyModel { pi_u: Some(pi_u), ..FamilyModel::thermal(pi_t) }
+        }
+        "ic" => FamilyModel::thermal(ic_model(comp.technology.as_deref())).at_junction(ctx),
+        "diode" => FamilyModel::with_voltage(PiTModel::single(0.4, THETA_REF), 2.0, 1.0).at_junction(ctx),
+        "transistor" => FamilyModel::with_voltage(PiTModel::single(0.4, THETA_REF), 2.0, 1.2).at_junction(ctx),
+        "optocoupler" => FamilyModel::with_current(PiTModel::single(0.5, THETA_REF), 1.0, 1.2).at_junction(ctx),
+        "led" => FamilyModel::with_current(PiTModel::single(0.3, THETA_REF), 1.0, 1.0).at_junction(ctx),
+        "inductor" | "transformer" | "relay" | "switch" | "connector" | "crystal" | "oscillator"
+        | "fuse" => {
+            let m = part_model(&comp_type);
+            FamilyModel { pi_i: m.pi_i, ..FamilyModel::thermal(m.pi_t) }
+        }
+        _ => return None,
+    };
+    Some(model)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::StressData;
+    use crate::test_support::{component, profile, variant};
+
+    fn fit(ctx: &CalcContext) -> f64 {
+        Iec61709.calc_fit(ctx).unwrap()
+    }
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= 1e-6 * expected.abs()
+    }
+
+    #[test]
+    fn reference_conditions_give_the_reference_fit() {
+        let (comp, var, prof) = (component("resistor"), variant(2.0), profile(&[(40.0, 1.0)]));
+        assert!(close(fit(&CalcContext::new(&comp, &prof, Some(&var))), 2.0));
+    }
+
+    #[test]
+    fn resistor_and_film_capacitor_pi_t() {
+        // πT(70 °C) of the dual-Ea resistor model, πT(80 °C) of film (Ea = 0.15 eV)
+        let (comp, var, prof) = (component("resistor"), variant(1.0), profile(&[(70.0, 1.0)]));
+        assert!(close(fit(&CalcContext::new(&comp, &prof, Some(&var))), 1.995763));
+
+        let mut cap = component("capacitor");
+        cap.capacitor_type = Some("film".to_string());
+        let prof = profile(&[(80.0, 1.0)]);
+        assert!(close(fit(&CalcContext::new(&cap, &prof, Some(&var))), 1.877983));
+    }
+
+    #[test]
+    fn ceramic_capacitor_voltage_stress() {
+        // πU = exp{1.0·[0.8³ − 0.5³]}
+        let mut cap = component("capacitor");
+        cap.capacitor_type = Some("x7r".to_string());
+        let (var, prof) = (variant(1.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&cap, &prof, Some(&var))
+            .with_stress(StressData { voltage_ratio: Some(0.8), ..Default::default() });
+        assert!(close(fit(&ctx), 1.472556));
+    }
+
+    #[test]
+    fn ic_pi_t_is_taken_at_the_junction() {
+        // θj = θamb + 20 W·K/W × 1 W; reference FIT at θj = 55 °C
+        let (comp, var) = (component("ic"), variant(1.0));
+        let prof = profile(&[(30.0, 0.5), (70.0, 0.5)]);
+        let stress = StressData { thermal_resistance: Some(20.0), power_dissipation: Some(1.0), ..Default::default() };
+        let ctx = CalcContext::new(&comp, &prof, Some(&var)).with_stress(stress);
+        assert!(close(fit(&ctx), 2.212680));
+
+        let pi_t = Iec61709.segment_pi_t(&ctx).unwrap();
+        assert!(close(0.5 * pi_t[0] + 0.5 * pi_t[1], 2.212680));
+
+        // Without self-heating data θj = θamb
+        let ambient = CalcContext::new(&comp, &prof, Some(&var));
+        assert!(fit(&ambient) < fit(&ctx));
+    }
+
+    #[test]
+    fn unknown_type_keeps_the_reference_fit() {
+        let (comp, var, prof) = (component("widget"), variant(3.0), profile(&[(90.0, 1.0)]));
+        assert_eq!(fit(&CalcContext::new(&comp, &prof, Some(&var))), 3.0);
+        assert!(Iec61709.calc_fit(&CalcContext::new(&comp, &prof, None)).is_err());
+    }
+}
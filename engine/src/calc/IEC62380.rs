@@ -1,27 +1,283 @@
-use crate::models::Component;
+use crate::errors::FmedaError;
+use crate::models::{Component, MissionSegment};
+use super::standard::{CalcContext, ReliabilityStandard, StressData};
 
-pub fn calc_fit(component: &Component) -> f64 {
-    match component.component_type.as_str() {
-        "Resistor" => calc_resistor(component),
-        "Capacitor" => calc_capacitor(component),
-        "IC" => calc_ic(component),
-        _ => 0.0,
+/// Package term constant for integrated circuits (IEC TR 62380 §7.3.1)
+const IC_PACKAGE_CONST: f64 = 2.75e-3;
+
+/// Package term constant for passive components (IEC TR 62380 §10, §11)
+const PASSIVE_PACKAGE_CONST: f64 = 1.4e-3;
+
+/// Thermal expansion coefficient of an FR4 substrate αS (ppm/°C)
+const ALPHA_SUBSTRATE: f64 = 16.0;
+
+/// Reference year of the die failure-rate data (a = year − 1998)
+const REFERENCE_YEAR: i32 = 1998;
+
+/// Die failure-rate data per technology: λ1 (per transistor), λ2 (per technology), Ea term A, Tref (K)
+struct DieData {
+    lambda1: f64,
+    lambda2: f64,
+    a: f64,
+    t_ref: f64,
+}
+
+/// Package data: λ3 = k·S^e with S the pin count, and component CTE αC (ppm/°C)
+struct PackageData {
+    k: f64,
+    exponent: f64,
+    alpha_c: f64,
+}
+
+/// IEC TR 62380 reliability data handbook
+pub struct Iec62380;
+
+impl ReliabilityStandard for Iec62380 {
+    fn name(&self) -> &str {
+        "IEC62380"
+    }
+
+    fn calc_fit(&self, ctx: &CalcContext) -> Result<f64, FmedaError> {
+        // IEC62380 is driven by component data and mission profile; variant is optional
+        calc_fit(ctx)
+    }
+}
+
+/// Entry point: IEC TR 62380 FIT from component data and mission profile
+pub fn calc_fit(ctx: &CalcContext) -> Result<f64, FmedaError> {
+    let component = ctx.component;
+    let variant_opt = ctx.variant;
+    let phases = ctx.profile.segments();
+    if phases.is_empty() {
+        return Err(FmedaError::EmptyMissionProfile);
+    }
+
+    let comp_type = component.component_type.to_lowercase();
+    let fit = match comp_type.as_str() {
+        "ic" => calc_ic(component, &ctx.stress, ctx.ea_scale, phases),
+        "resistor" => {
+            let lambda0 = variant_opt.map_or(0.1, |v| v.reference_fit());
+            calc_passive(lambda0, 1740.0 * ctx.ea_scale, 303.0, phases)
+        }
+        "capacitor" => {
+            let lambda0 = variant_opt.map_or(0.15, |v| v.reference_fit());
+            match component.capacitor_type.as_deref().map(str::to_lowercase).as_deref() {
+                Some("aluminium") | Some("aluminum") => calc_passive(lambda0, 4640.0 * ctx.ea_scale, 313.0, phases),
+                _ => calc_passive(lambda0, 1160.0 * ctx.ea_scale, 303.0, phases),
+            }
+        }
+        _ => {
+            let variant = variant_opt.ok_or_else(|| FmedaError::MissingVariant("IEC62380".to_string()))?;
+            report!(
+                "[WARN] Unknown component type '{}' for IEC62380, using reference FIT {:.6}",
+                comp_type, variant.reference_fit()
+            );
+            return Ok(variant.reference_fit());
+        }
+    };
+
+    report!(
+        "[IEC62380] {} | {} → FIT = {:.6}",
+        component.manufacturer_part_number, comp_type, fit
+    );
+    Ok(fit)
+}
+
+/// Integrated circuit: die + package + EOS
+///
+/// λ = [λ1·N·e^(−0.35a) + λ2]·Σ(πt·τi)/(τon+τoff)
+///     + 2.75e-3·πα·Σ(πn·ΔTi^0.68)·λ3
+///     + πI·λEOS
+fn calc_ic(comp: &Component, stress: &StressData, ea_scale: f64, phases: &[MissionSegment]) -> f64 {
+    let die = die_data(comp.technology.as_deref());
+    let n = comp.transistor_count.unwrap_or(0) as f64;
+    if comp.transistor_count.is_none() {
+        report!("[WARN] {}: transistor count missing → die term uses λ2 only", comp.manufacturer_part_number);
+    }
+    let a = (comp.manufacture_year.unwrap_or(REFERENCE_YEAR) - REFERENCE_YEAR).max(0) as f64;
+
+    // Junction temperature rise from dissipated power
+    let self_heating = stress.thermal_resistance.unwrap_or(0.0) * stress.power_dissipation.unwrap_or(0.0);
+
+    let pi_t_avg = time_weighted(phases, |temp| {
+        let tj = temp + self_heating;
+        (die.a * ea_scale * (1.0 / die.t_ref - 1.0 / (273.0 + tj))).exp()
+    });
+    let lambda_die = (die.lambda1 * n * (-0.35 * a).exp() + die.lambda2) * pi_t_avg;
+
+    let package = package_data(comp.package_type.as_deref());
+    let pins = comp.pin_count.unwrap_or(8).max(1) as f64;
+    let lambda3 = package.k * pins.powf(package.exponent);
+    let pi_alpha = 0.06 * (ALPHA_SUBSTRATE - package.alpha_c).abs().powf(1.68);
+    let lambda_package = IC_PACKAGE_CONST * pi_alpha * cycling_sum(phases) * lambda3;
+
+    let lambda_eos = eos_lambda(comp.interface_category.as_deref());
+
+    report!(
+        "[IEC62380] die = {:.6} | package = {:.6} | EOS = {:.6}",
+        lambda_die, lambda_package, lambda_eos
+    );
+
+    lambda_die + lambda_package + lambda_eos
+}
+
+/// Passive component: λ = λ0·[Σ(πt·τi)/(τon+τoff) + 1.4e-3·Σ(πn·ΔTi^0.68)]
+/// with πt = exp(A·(1/Tref − 1/T))
+fn calc_passive(lambda0: f64, a: f64, t_ref: f64, phases: &[MissionSegment]) -> f64 {
+    let pi_t_avg = time_weighted(phases, |temp| (a * (1.0 / t_ref - 1.0 / (273.0 + temp))).exp());
+    lambda0 * (pi_t_avg + PASSIVE_PACKAGE_CONST * cycling_sum(phases))
+}
+
+/// Σ(πt·τi) over working phases divided by the total time τon + τoff
+fn time_weighted(phases: &[MissionSegment], pi_t: impl Fn(f64) -> f64) -> f64 {
+    let total_tau: f64 = phases.iter().map(|p| p.tau).sum();
+    if total_tau == 0.0 {
+        return 0.0;
+    }
+    phases
+        .iter()
+        .filter(|p| p.on)
+        .map(|p| pi_t(p.temperature) * p.tau)
+        .sum::<f64>()
+        / total_tau
+}
+
+/// Σ(πn·ΔTi^0.68) over the phases with thermal cycling,
+/// where πn = n^0.76 up to 8760 cycles/year, 1.7·n^0.6 above
+fn cycling_sum(phases: &[MissionSegment]) -> f64 {
+    phases
+        .iter()
+        .filter_map(|p| Some((p.delta_t?, p.cycles_per_year?)))
+        .map(|(delta_t, n)| {
+            let pi_n = if n <= 8760.0 {
+                n.powf(0.76)
+            } else {
+                1.7 * n.powf(0.6)
+            };
+            pi_n * delta_t.abs().powf(0.68)
+        })
+        .sum()
+}
+
+/// IEC TR 62380 die data by technology (default: digital CMOS)
+fn die_data(technology: Option<&str>) -> DieData {
+    const MOS_A: f64 = 3480.0;
+    const MOS_TREF: f64 = 328.0;
+    const BIPOLAR_A: f64 = 4640.0;
+    const BIPOLAR_TREF: f64 = 373.0;
+
+    match technology.map(str::to_lowercase).as_deref() {
+        Some("microcontroller") | Some("microprocessor") => DieData { lambda1: 3.4e-6, lambda2: 1.7, a: MOS_A, t_ref: MOS_TREF },
+        Some("sram") => DieData { lambda1: 1.7e-7, lambda2: 8.8, a: MOS_A, t_ref: MOS_TREF },
+        Some("dram") => DieData { lambda1: 1.0e-7, lambda2: 5.6, a: MOS_A, t_ref: MOS_TREF },
+        Some("flash") | Some("eeprom") => DieData { lambda1: 2.6e-7, lambda2: 34.0, a: MOS_A, t_ref: MOS_TREF },
+        Some("analog") | Some("bipolar") => DieData { lambda1: 2.7e-2, lambda2: 20.0, a: BIPOLAR_A, t_ref: BIPOLAR_TREF },
+        Some("mixed") | Some("bicmos") => DieData { lambda1: 2.7e-4, lambda2: 20.0, a: MOS_A, t_ref: MOS_TREF },
+        _ => DieData { lambda1: 3.4e-6, lambda2: 1.7, a: MOS_A, t_ref: MOS_TREF },
     }
 }
 
-fn calc_resistor(comp: &Component) -> f64 {
-    // IEC 62380 uses environment-dependent model
-    // λ = C1 * πT * πU
-    let c1 = 4.0;
-    let temp_factor = 1.2;
-    let usage_factor = 1.1;
-    c1 * temp_factor * usage_factor
+/// IEC TR 62380 package data by package family (default: SO)
+fn package_data(package: Option<&str>) -> PackageData {
+    match package.map(str::to_lowercase).as_deref() {
+        Some("dip") => PackageData { k: 0.014, exponent: 1.20, alpha_c: 21.5 },
+        Some("tssop") => PackageData { k: 0.011, exponent: 1.40, alpha_c: 21.5 },
+        Some("qfp") => PackageData { k: 0.0022, exponent: 1.67, alpha_c: 21.5 },
+        Some("qfn") => PackageData { k: 0.0060, exponent: 1.60, alpha_c: 21.5 },
+        Some("bga") => PackageData { k: 0.0014, exponent: 1.70, alpha_c: 21.5 },
+        Some("sot") => PackageData { k: 0.0098, exponent: 1.35, alpha_c: 21.5 },
+        Some("ceramic") => PackageData { k: 0.012, exponent: 1.65, alpha_c: 6.5 },
+        _ => PackageData { k: 0.012, exponent: 1.65, alpha_c: 21.5 },
+    }
 }
 
-fn calc_capacitor(comp: &Component) -> f64 {
-    3.0 * 1.1 * 1.05
+/// λEOS per interface category; πI = 0 for non-interface circuits
+fn eos_lambda(category: Option<&str>) -> f64 {
+    match category.map(str::to_lowercase).as_deref() {
+        None | Some("none") => 0.0,
+        Some("computer") => 10.0,
+        Some("telecom_switching") => 15.0,
+        Some("avionics") => 20.0,
+        Some("telecom_access") | Some("voltage_supply") => 40.0,
+        Some("telecom_subscriber") => 70.0,
+        Some("railway") => 100.0,
+        Some(other) => {
+            report!("[WARN] Unknown interface category '{}' → λEOS = 0", other);
+            0.0
+        }
+    }
 }
 
-fn calc_ic(comp: &Component) -> f64 {
-    6.0 * 1.3 * 1.2
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{component, profile, variant};
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= 1e-6 * expected.abs()
+    }
+
+    #[test]
+    fn resistor_pi_t_without_cycling() {
+        // λ0 = 0.1, πt = exp(1740·(1/303 − 1/313)) at 40 °C
+        let (comp, prof) = (component("resistor"), profile(&[(40.0, 1.0)]));
+        assert!(close(calc_fit(&CalcContext::new(&comp, &prof, None)).unwrap(), 0.1201378));
+    }
+
+    #[test]
+    fn aluminium_capacitor_uses_its_activation_term() {
+        let mut comp = component("capacitor");
+        comp.capacitor_type = Some("aluminium".to_string());
+        let (var, prof) = (variant(1.0), profile(&[(80.0, 1.0)]));
+        assert!(close(calc_fit(&CalcContext::new(&comp, &prof, Some(&var))).unwrap(), 5.364511));
+    }
+
+    #[test]
+    fn ic_die_package_and_eos_terms() {
+        // CMOS, N = 1e5, a = 0, θj = 55 °C (πt = 1): die = 3.4e-6·1e5 + 1.7 = 2.04
+        // SO-8 with 1000 cycles/year of ΔT = 20 °C: package = 1.567738
+        let mut comp = component("ic");
+        comp.transistor_count = Some(100_000);
+        comp.manufacture_year = Some(REFERENCE_YEAR);
+        comp.pin_count = Some(8);
+        let mut prof = profile(&[(55.0, 1.0)]);
+        prof.temp_tau_profile.segments[0].delta_t = Some(20.0);
+        prof.temp_tau_profile.segments[0].cycles_per_year = Some(1000.0);
+        assert!(close(calc_fit(&CalcContext::new(&comp, &prof, None)).unwrap(), 3.607738));
+
+        comp.interface_category = Some("voltage_supply".to_string());
+        assert!(close(calc_fit(&CalcContext::new(&comp, &prof, None)).unwrap(), 43.607738));
+    }
+
+    #[test]
+    fn dormant_phases_do_not_age_the_die() {
+        let mut comp = component("ic");
+        comp.transistor_count = Some(100_000);
+        let mut prof = profile(&[(55.0, 0.5), (20.0, 0.5)]);
+        prof.temp_tau_profile.segments[1].on = false;
+        assert!(close(calc_fit(&CalcContext::new(&comp, &prof, None)).unwrap(), 1.02));
+    }
+
+    #[test]
+    fn thermal_cycling_factor_above_8760_cycles() {
+        let mut prof = profile(&[(40.0, 1.0)]);
+        prof.temp_tau_profile.segments[0].delta_t = Some(1.0);
+        prof.temp_tau_profile.segments[0].cycles_per_year = Some(10_000.0);
+        assert!(close(cycling_sum(prof.segments()), 427.020693));
+    }
+
+    #[test]
+    fn empty_profile_and_unknown_type_without_variant_are_errors() {
+        let comp = component("resistor");
+        let empty = profile(&[]);
+        assert!(matches!(
+            calc_fit(&CalcContext::new(&comp, &empty, None)),
+            Err(FmedaError::EmptyMissionProfile)
+        ));
+
+        let (widget, prof) = (component("widget"), profile(&[(40.0, 1.0)]));
+        assert!(matches!(calc_fit(&CalcContext::new(&widget, &prof, None)), Err(FmedaError::MissingVariant(_))));
+        let var = variant(4.0);
+        assert_eq!(calc_fit(&CalcContext::new(&widget, &prof, Some(&var))).unwrap(), 4.0);
+    }
 }
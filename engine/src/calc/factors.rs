@@ -0,0 +1,247 @@
+use super::standard::StressData;
+
+/// Conversion constant q/k (K/eV) used in the Arrhenius terms of πT
+pub const EV_TO_KELVIN: f64 = 11605.0;
+
+/// Offset used by the handbooks to convert °C to K
+pub const KELVIN_OFFSET: f64 = 273.0;
+
+/// Temperature dependence with two activation energies, as used by
+/// SN 29500 and IEC 61709:
+///
+/// πT = [A·e^(Ea1·z) + (1−A)·e^(Ea2·z)] / [A·e^(Ea1·zref) + (1−A)·e^(Ea2·zref)]
+///
+/// with z = 11605·(1/Tref − 1/T2) and zref = 11605·(1/Tref − 1/T1).
+/// A single activation energy is expressed with A = 1.
+#[derive(Debug, Clone, Copy)]
+pub struct PiTModel {
+    pub a: f64,
+    pub ea1: f64,
+    pub ea2: f64,
+    /// Reference temperature Tref of the model (°C)
+    pub theta_ref: f64,
+}
+
+impl PiTModel {
+    pub const fn dual(a: f64, ea1: f64, ea2: f64, theta_ref: f64) -> Self {
+        Self { a, ea1, ea2, theta_ref }
+    }
+
+    pub const fn single(ea: f64, theta_ref: f64) -> Self {
+        Self { a: 1.0, ea1: ea, ea2: 0.0, theta_ref }
+    }
+
+    /// Model with both activation energies multiplied by `factor`
+    /// (uncertainty studies); `factor` = 1 leaves it unchanged
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self { ea1: self.ea1 * factor, ea2: self.ea2 * factor, ..*self }
+    }
+
+    /// πT for operation at `theta` when the reference FIT is given at `theta_ref_fit`
+    pub fn pi_t(&self, theta_ref_fit: f64, theta: f64) -> f64 {
+        let tref = self.theta_ref + KELVIN_OFFSET;
+        let t1 = theta_ref_fit + KELVIN_OFFSET;
+        let t2 = theta + KELVIN_OFFSET;
+
+        let z = EV_TO_KELVIN * ((1.0 / tref) - (1.0 / t2));
+        let zref = EV_TO_KELVIN * ((1.0 / tref) - (1.0 / t1));
+
+        let numerator = self.a * (self.ea1 * z).exp() + (1.0 - self.a) * (self.ea2 * z).exp();
+        let denominator = self.a * (self.ea1 * zref).exp() + (1.0 - self.a) * (self.ea2 * zref).exp();
+
+        numerator / denominator
+    }
+
+    /// τ-weighted πT over (temperature, τ) segments.
+    /// Returns `None` when there is nothing to weight.
+    pub fn weighted(&self, segments: &[(f64, f64)], theta_ref_fit: f64) -> Option<f64> {
+        let total_tau: f64 = segments.iter().map(|(_, tau)| *tau).sum();
+        if segments.is_empty() || total_tau == 0.0 {
+            return None;
+        }
+
+        Some(
+            segments
+                .iter()
+                .map(|(temp, tau)| self.pi_t(theta_ref_fit, *temp) * (*tau / total_tau))
+                .sum(),
+        )
+    }
+}
+
+/// Electrical stress dependence (πU for voltage, πI for current):
+///
+/// π = exp{C3·[(S/Srat)^C2 − (Sref/Srat)^C2]}
+///
+/// where S/Srat is the applied-to-rated ratio and Sref/Srat the ratio
+/// at which the reference FIT is stated.
+#[derive(Debug, Clone, Copy)]
+pub struct StressModel {
+    pub c2: f64,
+    pub c3: f64,
+    pub ref_ratio: f64,
+}
+
+impl StressModel {
+    pub const fn new(c2: f64, c3: f64, ref_ratio: f64) -> Self {
+        Self { c2, c3, ref_ratio }
+    }
+
+    /// Stress factor for the given ratio; without a ratio the part is
+    /// assumed to run at reference stress (factor 1).
+    pub fn factor(&self, ratio: Option<f64>) -> f64 {
+        match ratio {
+            Some(r) => (self.c3 * (r.max(0.0).powf(self.c2) - self.ref_ratio.powf(self.c2))).exp(),
+            None => 1.0,
+        }
+    }
+}
+
+/// Reference ambient temperature of the πT models (°C)
+pub const THETA_REF: f64 = 40.0;
+
+/// Reference virtual junction temperature of semiconductors (°C)
+pub const THETA_REF_JUNCTION: f64 = 55.0;
+
+/// Reference voltage ratio Uref/Urat
+pub const U_REF_RATIO: f64 = 0.5;
+
+/// Reference current ratio Iref/Irat
+pub const I_REF_RATIO: f64 = 0.5;
+
+// The component-family tables below are shared by SN 29500 and IEC 61709,
+// which state the same models for these families.
+
+/// Film / metal-oxide resistor temperature model (SN 29500-7, IEC 61709)
+pub const RESISTOR_PIT: PiTModel = PiTModel::dual(0.873, 0.16, 0.44, THETA_REF);
+
+/// πT and πU models per capacitor dielectric (default: ceramic class 2)
+pub fn capacitor_model(capacitor_type: Option<&str>) -> (PiTModel, StressModel) {
+    match capacitor_type.map(str::to_lowercase).as_deref() {
+        Some("ceramic class 1") | Some("c0g") | Some("np0") => {
+            (PiTModel::single(0.35, THETA_REF), StressModel::new(3.0, 0.5, U_REF_RATIO))
+        }
+        Some("ceramic") | Some("ceramic class 2") | Some("x7r") | Some("x5r") => {
+            (PiTModel::single(0.35, THETA_REF), StressModel::new(3.0, 1.0, U_REF_RATIO))
+        }
+        Some("tantalum") => (PiTModel::single(0.35, THETA_REF), StressModel::new(3.0, 1.4, U_REF_RATIO)),
+        Some("aluminium") | Some("aluminum") => {
+            (PiTModel::single(0.5, THETA_REF), StressModel::new(5.0, 1.0, U_REF_RATIO))
+        }
+        Some("film") => (PiTModel::single(0.15, THETA_REF), StressModel::new(4.0, 0.8, U_REF_RATIO)),
+        other => {
+            report!("[WARN] Unknown capacitor type {:?} → using ceramic class 2 model", other);
+            (PiTModel::single(0.35, THETA_REF), StressModel::new(3.0, 1.0, U_REF_RATIO))
+        }
+    }
+}
+
+/// πT model per IC technology family at the virtual junction (default: digital CMOS)
+pub fn ic_model(technology: Option<&str>) -> PiTModel {
+    match technology.map(str::to_lowercase).as_deref() {
+        Some("sram") | Some("dram") | Some("flash") | Some("eeprom") | Some("memory") => {
+            PiTModel::dual(0.8, 0.3, 0.6, THETA_REF_JUNCTION)
+        }
+        Some("analog") | Some("bipolar") | Some("mixed") | Some("bicmos") => {
+            PiTModel::dual(0.9, 0.4, 0.7, THETA_REF_JUNCTION)
+        }
+        _ => PiTModel::dual(0.9, 0.3, 0.7, THETA_REF_JUNCTION),
+    }
+}
+
+/// Switching-rate dependence of relays and switches:
+/// πW = 1 for W ≤ Wref, W / Wref above (W in operations per hour)
+#[derive(Debug, Clone, Copy)]
+pub struct SwitchingModel {
+    pub w_ref: f64,
+}
+
+impl SwitchingModel {
+    pub fn factor(&self, rate: Option<f64>) -> f64 {
+        match rate {
+            Some(w) if w > self.w_ref => w / self.w_ref,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Stress models of an inductive, electromechanical or connecting part
+pub struct PartModel {
+    pub pi_t: PiTModel,
+    pub pi_i: Option<StressModel>,
+    pub pi_w: Option<SwitchingModel>,
+}
+
+/// Models per part family: inductor, transformer, relay, switch,
+/// connector, crystal, oscillator and fuse
+pub fn part_model(comp_type: &str) -> PartModel {
+    let thermal = |pi_t| PartModel { pi_t, pi_i: None, pi_w: None };
+
+    match comp_type {
+        "inductor" | "transformer" => thermal(PiTModel::single(0.15, THETA_REF)),
+        "relay" => PartModel {
+            pi_t: PiTModel::single(0.23, THETA_REF),
+            pi_i: Some(StressModel::new(2.0, 1.0, I_REF_RATIO)),
+            pi_w: Some(SwitchingModel { w_ref: 1.0 }),
+        },
+        "switch" => PartModel {
+            pi_t: PiTModel::single(0.1, THETA_REF),
+            pi_i: Some(StressModel::new(2.0, 1.0, I_REF_RATIO)),
+            pi_w: Some(SwitchingModel { w_ref: 0.5 }),
+        },
+        "connector" => thermal(PiTModel::single(0.14, THETA_REF)),
+        "crystal" | "oscillator" => thermal(PiTModel::single(0.3, THETA_REF)),
+        _ => thermal(PiTModel::single(0.1, THETA_REF)),
+    }
+}
+
+/// Junction temperature rise Rth × P (K); zero when either value is missing
+pub fn junction_temperature_rise(stress: &StressData) -> f64 {
+    match (stress.thermal_resistance, stress.power_dissipation) {
+        (Some(rth), Some(p)) => rth * p,
+        _ => {
+            report!("[WARN] Rth or power dissipation missing → θj = θamb");
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= 1e-6 * expected.abs()
+    }
+
+    #[test]
+    fn pi_t_is_one_at_the_reference_fit_temperature() {
+        assert!(close(RESISTOR_PIT.pi_t(55.0, 55.0), 1.0));
+        assert!(close(ic_model(None).pi_t(THETA_REF_JUNCTION, THETA_REF_JUNCTION), 1.0));
+    }
+
+    #[test]
+    fn dual_activation_energy_pi_t() {
+        // Resistor model, reference FIT at 55 °C, operation at 85 °C
+        assert!(close(RESISTOR_PIT.pi_t(55.0, 85.0), 2.001086));
+    }
+
+    #[test]
+    fn weighted_pi_t_uses_tau_shares() {
+        // τ = 0.25 at 20 °C and 0.75 at 60 °C, single Ea = 0.35 eV
+        let model = PiTModel::single(0.35, THETA_REF);
+        let weighted = model.weighted(&[(20.0, 0.25), (60.0, 0.75)], THETA_REF).unwrap();
+        assert!(close(weighted, 1.738203));
+        assert!(model.weighted(&[], THETA_REF).is_none());
+        assert!(model.weighted(&[(20.0, 0.0)], THETA_REF).is_none());
+    }
+
+    #[test]
+    fn stress_factor_is_one_at_reference_stress() {
+        let model = StressModel::new(2.0, 1.2, U_REF_RATIO);
+        assert!(close(model.factor(Some(0.5)), 1.0));
+        assert!(close(model.factor(None), 1.0));
+        assert!(close(model.factor(Some(0.9)), 1.958150));
+        assert!(close(model.factor(Some(-1.0)), 0.740818));
+    }
+}
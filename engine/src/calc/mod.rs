@@ -16,31 +16,30 @@
 //         _ => Err(format!("Unknown standard: {}", standard).into()),
 //     }
 // }
+pub mod factors;
+pub mod standard;
 pub mod sn29500;
+#[allow(non_snake_case)]
+pub mod IEC61709;
+#[allow(non_snake_case)]
 pub mod IEC62380;
 
+use crate::errors::FmedaError;
 use crate::models::{Component, ComponentVariant, MissionProfile};
-use std::error::Error;
+pub use standard::{CalcContext, ReliabilityStandard, StandardRegistry, StressData};
 
-/// Generic interface to calculate FIT based on reliability standard
+/// Conversion from FIT (failures per 10⁹ h) to failures per hour
+pub const FIT_TO_PER_HOUR: f64 = 1e-9;
+
+/// Generic interface to calculate FIT based on reliability standard,
+/// looked up by name in the given registry
 pub fn calculate_fit(
+    registry: &StandardRegistry,
     standard: &str,
     component: &Component,
     profile: &MissionProfile,
     variant_opt: Option<&ComponentVariant>,
-) -> Result<f64, Box<dyn Error>> {
-    match standard {
-        "SN29500" => {
-            // Use variant info from migration 0004 for SN29500
-            if variant_opt.is_none() {
-                return Err("Component variant not set; cannot calculate SN29500 FIT".into());
-            }
-            Ok(sn29500::calc_fit(component, profile, variant_opt))
-        }
-        "IEC62380" => {
-            // IEC62380 does not need mission profile or variant
-            Ok(IEC62380::calc_fit(component))
-        }
-        _ => Err(format!("Unknown standard: {}", standard).into()),
-    }
+) -> Result<f64, FmedaError> {
+    let ctx = CalcContext::new(component, profile, variant_opt);
+    registry.calculate_fit(standard, &ctx)
 }
@@ -127,26 +127,157 @@
 //     pairs
 // }
 
-use crate::models::{Component, ComponentVariant, MissionProfile};
-use serde_json::Value;
+use crate::errors::FmedaError;
+use super::factors::{
+    capacitor_model, ic_model, junction_temperature_rise, part_model, PiTModel, StressModel, SwitchingModel,
+    I_REF_RATIO, RESISTOR_PIT, THETA_REF, THETA_REF_JUNCTION, U_REF_RATIO,
+};
+use super::standard::{CalcContext, ReliabilityStandard};
 
 /// Entry point: calculate FIT based on component type and mission profile
+///
+/// λ = λref × ΣWeighted(πT) × πU × πI × πW, where the factors a component
+/// family does not model are 1. ICs and discrete semiconductors take πT at the
+/// junction temperature θj = θamb + Rth × P of each segment.
 pub fn calc_fit(
-    comp: &Component,
-    profile: &MissionProfile,
-    variant_opt: Option<&ComponentVariant>,
+    ctx: &CalcContext,
 ) -> f64 {
-    let comp_type = comp.component_type.to_lowercase();
+    let comp_type = ctx.component.component_type.to_lowercase();
+
+    let Some(variant) = ctx.variant else {
+        report!("[WARN] Component variant missing → using base FIT");
+        return get_base_lambda(&comp_type);
+    };
+    let Some(model) = component_model(ctx) else {
+        report!("[WARN] Unknown component type '{}', using base λ (lambda).", comp_type);
+        return get_base_lambda(&comp_type);
+    };
+
+    let ref_fit = variant.reference_fit();
+    let theta_ref_fit = variant.ref_temp.unwrap_or(model.theta_ref);
+
+    let Some(weighted_pit_sum) = weighted_pit(&model.pi_t, ctx, theta_ref_fit, model.temp_rise) else {
+        return ref_fit;
+    };
+
+    let pi_u = match model.pi_u {
+        Some(m) => {
+            if ctx.stress.voltage_ratio.is_none() {
+                report!("[WARN] Voltage ratio U/Urat missing → πU = 1 (reference stress)");
+            }
+            m.factor(ctx.stress.voltage_ratio)
+        }
+        None => 1.0,
+    };
+    let pi_i = model.pi_i.map_or(1.0, |m| m.factor(ctx.stress.current_ratio));
+    let pi_w = match model.pi_w {
+        Some(m) => {
+            if ctx.stress.switching_rate.is_none() {
+                report!("[WARN] Switching rate missing → πW = 1");
+            }
+            m.factor(ctx.stress.switching_rate)
+        }
+        None => 1.0,
+    };
 
-    match comp_type.as_str() {
-        "resistor" => calc_resistor_sn29500(comp, profile, variant_opt),
-        "capacitor" => calc_capacitor_sn29500(profile),
-        "ic" => calc_ic_sn29500(profile),
-        _ => {
-            println!("[WARN] Unknown component type '{}', using base λ (lambda).", comp_type);
-            get_base_lambda(&comp_type)
+    let fit = ref_fit * weighted_pit_sum * pi_u * pi_i * pi_w;
+    report!(
+        "\n[SN29500] ref_fit = {:.6} | ΔT = {:.2} K | ΣWeighted(PiT) = {:.6} | PiU = {:.6} | PiI = {:.6} | PiW = {:.6} → Final FIT = {:.6} FIT",
+        ref_fit, model.temp_rise, weighted_pit_sum, pi_u, pi_i, pi_w, fit
+    );
+
+    fit
+}
+
+/// SN 29500 (Siemens) reliability standard
+pub struct Sn29500;
+
+impl ReliabilityStandard for Sn29500 {
+    fn name(&self) -> &str {
+        "SN29500"
+    }
+
+    fn calc_fit(&self, ctx: &CalcContext) -> Result<f64, FmedaError> {
+        // Use variant info from migration 0004 for SN29500
+        if ctx.variant.is_none() {
+            return Err(FmedaError::MissingVariant(self.name().to_string()));
         }
+        Ok(calc_fit(ctx))
     }
+
+    fn segment_pi_t(&self, ctx: &CalcContext) -> Option<Vec<f64>> {
+        let variant = ctx.variant?;
+        let model = component_model(ctx)?;
+        let pi_t = model.pi_t.scaled(ctx.ea_scale);
+        let theta_ref_fit = variant.ref_temp.unwrap_or(model.theta_ref);
+        Some(
+            ctx.profile
+                .segments()
+                .iter()
+                .map(|s| pi_t.pi_t(theta_ref_fit, s.temperature + model.temp_rise))
+                .collect(),
+        )
+    }
+}
+
+/// SN29500 model of one component: πT with its default reference temperature
+/// and the temperature rise over ambient, plus the stress factors of the family
+struct ComponentModel {
+    pi_t: PiTModel,
+    theta_ref: f64,
+    temp_rise: f64,
+    pi_u: Option<StressModel>,
+    pi_i: Option<StressModel>,
+    pi_w: Option<SwitchingModel>,
+}
+
+/// Select the SN29500 model of the component family. This is the only place
+/// the πT model is chosen, for both the FIT and the per-segment πT.
+/// `None` when SN29500 has no model for the component type.
+fn component_model(ctx: &CalcContext) -> Option<ComponentModel> {
+    let comp = ctx.component;
+    let comp_type = comp.component_type.to_lowercase();
+    let thermal = |pi_t, theta_ref, temp_rise| ComponentModel {
+        pi_t,
+        theta_ref,
+        temp_rise,
+        pi_u: None,
+        pi_i: None,
+        pi_w: None,
+    };
+
+    let model = match comp_type.as_str() {
+        "resistor" => thermal(RESISTOR_PIT, THETA_REF, 0.0),
+        "capacitor" => {
+            let (pi_t, pi_u) = capacitor_model(comp.capacitor_type.as_deref());
+            ComponentModel { pi_u: Some(pi_u), ..thermal(pi_t, THETA_REF, 0.0) }
+        }
+        "ic" => thermal(
+            ic_model(comp.technology.as_deref()),
+            THETA_REF_JUNCTION,
+            junction_temperature_rise(&ctx.stress),
+        ),
+        "diode" | "transistor" | "optocoupler" => {
+            let m = discrete_model(&comp_type, comp.semiconductor_type.as_deref());
+            ComponentModel {
+                pi_u: m.pi_u,
+                pi_i: m.pi_i,
+                ..thermal(m.pi_t, THETA_REF_JUNCTION, junction_temperature_rise(&ctx.stress))
+            }
+        }
+        "inductor" | "transformer" | "relay" | "switch" | "connector" | "crystal" | "oscillator"
+        | "fuse" => {
+            let m = part_model(&comp_type);
+            // Winding / contact hot-spot rise, where the self-heating is known
+            let hot_spot_rise = match (ctx.stress.thermal_resistance, ctx.stress.power_dissipation) {
+                (Some(rth), Some(p)) => rth * p,
+                _ => 0.0,
+            };
+            ComponentModel { pi_i: m.pi_i, pi_w: m.pi_w, ..thermal(m.pi_t, THETA_REF, hot_spot_rise) }
+        }
+        _ => return None,
+    };
+    Some(model)
 }
 
 /// Fallback base λ (FIT) if type-specific model not defined
@@ -159,109 +290,175 @@ fn get_base_lambda(comp_type: &str) -> f64 {
     }
 }
 
-/// SN29500 Resistor FIT calculation using weighted PiT
-fn calc_resistor_sn29500(
-    comp: &Component,
-    profile: &MissionProfile,
-    variant_opt: Option<&ComponentVariant>,
-) -> f64 {
-    let variant = match variant_opt {
-        Some(v) => v,
-        None => {
-            println!("[WARN] Component variant missing → using base FIT");
-            return get_base_lambda(&comp.component_type);
-        }
-    };
+/// SN29500-3 stress models of a discrete semiconductor
+struct DiscreteModel {
+    pi_t: PiTModel,
+    pi_u: Option<StressModel>,
+    pi_i: Option<StressModel>,
+}
 
-    let ref_fit = variant.ref_fit;
-    // Use ref_temp unless zero, otherwise fallback to 40
-    let theta_ref_fit = variant.ref_temp.unwrap_or(40.0);
-    let thetaref = 40.0; // SN29500 reference temperature
+/// SN29500-3 models per discrete family and semiconductor type
+fn discrete_model(comp_type: &str, semiconductor_type: Option<&str>) -> DiscreteModel {
+    let voltage = |c2, c3| Some(StressModel::new(c2, c3, U_REF_RATIO));
+    let current = |c2, c3| Some(StressModel::new(c2, c3, I_REF_RATIO));
+    let sub = semiconductor_type.map(str::to_lowercase);
+
+    match (comp_type, sub.as_deref()) {
+        ("diode", Some("rectifier")) => DiscreteModel {
+            pi_t: PiTModel::single(0.4, THETA_REF_JUNCTION),
+            pi_u: voltage(2.0, 0.8),
+            pi_i: current(1.0, 0.6),
+        },
+        ("diode", Some("zener")) => DiscreteModel {
+            pi_t: PiTModel::single(0.4, THETA_REF_JUNCTION),
+            pi_u: None,
+            pi_i: current(1.0, 1.0),
+        },
+        ("diode", Some("tvs")) => DiscreteModel {
+            pi_t: PiTModel::single(0.4, THETA_REF_JUNCTION),
+            pi_u: voltage(3.0, 1.2),
+            pi_i: None,
+        },
+        ("diode", _) => DiscreteModel {
+            pi_t: PiTModel::single(0.4, THETA_REF_JUNCTION),
+            pi_u: voltage(2.0, 0.6),
+            pi_i: None,
+        },
+        ("transistor", Some("mosfet")) => DiscreteModel {
+            pi_t: PiTModel::single(0.5, THETA_REF_JUNCTION),
+            pi_u: voltage(3.0, 1.2),
+            pi_i: current(1.0, 0.8),
+        },
+        ("transistor", _) => DiscreteModel {
+            pi_t: PiTModel::single(0.5, THETA_REF_JUNCTION),
+            pi_u: voltage(2.0, 1.0),
+            pi_i: current(1.0, 0.8),
+        },
+        _ => DiscreteModel {
+            // Optocoupler: LED degradation dominates, driven by forward current
+            pi_t: PiTModel::dual(0.8, 0.3, 0.6, THETA_REF_JUNCTION),
+            pi_u: None,
+            pi_i: current(1.0, 1.2),
+        },
+    }
+}
 
-    let segments = extract_temp_tau_pairs(Some(&profile.temp_tau_profile));
+/// τ-weighted PiT over the mission-profile segments, printing each segment.
+/// `temp_rise` is added to each segment temperature (self-heating).
+fn weighted_pit(
+    model: &PiTModel,
+    ctx: &CalcContext,
+    theta_ref_fit: f64,
+    temp_rise: f64,
+) -> Option<f64> {
+    let model = model.scaled(ctx.ea_scale);
+    let segments = ctx.profile.temp_tau_profile.temp_tau_pairs();
     if segments.is_empty() {
-        println!("[WARN] No temperature–τ segments found → returning ref_fit = {}", ref_fit);
-        return ref_fit;
+        report!("[WARN] No temperature–τ segments found → returning ref_fit");
+        return None;
     }
 
     let total_tau: f64 = segments.iter().map(|(_, tau)| *tau).sum();
     if total_tau == 0.0 {
-        println!("[WARN] All τ values are zero → returning ref_fit = {}", ref_fit);
-        return ref_fit;
+        report!("[WARN] All τ values are zero → returning ref_fit");
+        return None;
     }
 
-    println!("\n[SN29500] --- Mission Profile: Temperature Segments ---");
+    report!("\n[SN29500] --- Mission Profile: Temperature Segments ---");
     let mut weighted_pit_sum = 0.0;
 
-    for (temp, tau) in &segments {
-        let pit = calc_pit_custom(thetaref, theta_ref_fit, *temp);
+    for (ambient, tau) in &segments {
+        let temp = ambient + temp_rise;
+        let pit = model.pi_t(theta_ref_fit, temp);
         let weight = *tau / total_tau;
         let weighted = pit * weight;
         weighted_pit_sum += weighted;
 
-        println!(
+        report!(
             "Temp = {:>6.2} °C | τ = {:>7.4} | PiT = {:>8.6} | Weighted = {:>8.6}",
             temp, tau, pit, weighted
         );
     }
 
-    let fit = ref_fit * weighted_pit_sum;
-    println!(
-        "\n[SN29500] ref_fit = {:.6} | ΣWeighted(PiT) = {:.6} → Final FIT = {:.6} FIT",
-        ref_fit, weighted_pit_sum, fit
-    );
-
-    fit
+    Some(weighted_pit_sum)
 }
 
-/// Placeholder functions for other component types
-fn calc_capacitor_sn29500(_profile: &MissionProfile) -> f64 { 0.8 }
-fn calc_ic_sn29500(_profile: &MissionProfile) -> f64 { 5.0 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::StressData;
+    use crate::test_support::{component, profile, variant};
 
-/// SN29500 PiT calculation
-fn calc_pit_custom(thetaref: f64, theta_ref_fit: f64, theta_mission: f64) -> f64 {
-    let a = 0.873;
-    let ea1 = 0.16;
-    let ea2 = 0.44;
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= 1e-6 * expected.abs()
+    }
 
-    let turef = thetaref + 273.0;
-    let tref_fit = theta_ref_fit + 273.0;
-    let tmission = theta_mission + 273.0;
+    fn fit(ctx: &CalcContext) -> f64 {
+        Sn29500.calc_fit(ctx).unwrap()
+    }
 
-    let z = 11605.0 * ((1.0 / turef) - (1.0 / tmission));
-    let zref = 11605.0 * ((1.0 / turef) - (1.0 / tref_fit));
+    #[test]
+    fn capacitor_voltage_stress() {
+        // Tantalum at the 40 °C reference: πU = exp{1.4·[0.8³ − 0.5³]}
+        let mut comp = component("capacitor");
+        comp.capacitor_type = Some("tantalum".to_string());
+        let (var, prof) = (variant(1.0), profile(&[(40.0, 1.0)]));
+        let stress = StressData { voltage_ratio: Some(0.8), ..Default::default() };
+        assert!(close(fit(&CalcContext::new(&comp, &prof, Some(&var)).with_stress(stress)), 1.719098));
+        // Without U/Urat the capacitor runs at reference stress
+        assert!(close(fit(&CalcContext::new(&comp, &prof, Some(&var))), 1.0));
+    }
 
-    let numerator = a * (ea1 * z).exp() + (1.0 - a) * (ea2 * z).exp();
-    let denominator = a * (ea1 * zref).exp() + (1.0 - a) * (ea2 * zref).exp();
+    #[test]
+    fn ic_pi_t_at_junction_temperature() {
+        // θj = θamb + 30 K/W × 0.5 W; reference FIT at θj = 55 °C
+        let (comp, var) = (component("ic"), variant(5.0));
+        let stress = StressData { thermal_resistance: Some(30.0), power_dissipation: Some(0.5), ..Default::default() };
 
-    numerator / denominator
-}
+        let at_ref = profile(&[(40.0, 1.0)]);
+        assert!(close(fit(&CalcContext::new(&comp, &at_ref, Some(&var)).with_stress(stress)), 5.0));
 
-/// Extract temperature–τ pairs from JSON mission profile
-fn extract_temp_tau_pairs(json_opt: Option<&Value>) -> Vec<(f64, f64)> {
-    let mut pairs = Vec::new();
-    let Some(json) = json_opt else { return pairs; };
-
-    if let Some(segs) = json.get("segments").and_then(|v| v.as_array()) {
-        for seg in segs {
-            if let (Some(t), Some(tau)) = (
-                seg.get("temperature").and_then(|v| v.as_f64()),
-                seg.get("tau").and_then(|v| v.as_f64()),
-            ) {
-                pairs.push((t, tau));
-            }
-        }
-    } else if let (Some(temps), Some(taus)) = (
-        json.get("temperature").and_then(|v| v.as_array()),
-        json.get("tau").and_then(|v| v.as_array()),
-    ) {
-        let n = temps.len().min(taus.len());
-        for i in 0..n {
-            if let (Some(t), Some(tau)) = (temps[i].as_f64(), taus[i].as_f64()) {
-                pairs.push((t, tau));
-            }
-        }
+        let hot = profile(&[(60.0, 1.0)]);
+        let ctx = CalcContext::new(&comp, &hot, Some(&var)).with_stress(stress);
+        assert!(close(fit(&ctx), 5.0 * 2.071531));
+        assert!(close(Sn29500.segment_pi_t(&ctx).unwrap()[0], 2.071531));
     }
 
-    pairs
+    #[test]
+    fn discrete_semiconductor_stress_factors() {
+        let var = variant(1.0);
+        let prof = profile(&[(55.0, 1.0)]);
+        let junction = StressData { thermal_resistance: Some(0.0), power_dissipation: Some(0.0), ..Default::default() };
+
+        // MOSFET at θj = 55 °C: πU = exp{1.2·[0.8³ − 0.5³]}, πI = exp{0.8·[0.8 − 0.5]}
+        let mut mosfet = component("transistor");
+        mosfet.semiconductor_type = Some("mosfet".to_string());
+        let stress = StressData { voltage_ratio: Some(0.8), current_ratio: Some(0.8), ..junction };
+        assert!(close(fit(&CalcContext::new(&mosfet, &prof, Some(&var)).with_stress(stress)), 2.022633));
+
+        // Zener at θj = 75 °C has no πU: πT(Ea = 0.4 eV)·exp{1.0·[0.9 − 0.5]}
+        let mut zener = component("diode");
+        zener.semiconductor_type = Some("zener".to_string());
+        let hot = profile(&[(75.0, 1.0)]);
+        let stress = StressData { voltage_ratio: Some(1.0), current_ratio: Some(0.9), ..junction };
+        assert!(close(fit(&CalcContext::new(&zener, &hot, Some(&var)).with_stress(stress)), 3.364766));
+    }
+
+    #[test]
+    fn relay_switching_rate_and_inductor_hot_spot() {
+        let var = variant(2.0);
+        let prof = profile(&[(40.0, 1.0)]);
+
+        // πW = W / Wref above Wref = 1 op/h, 1 at or below
+        let relay = component("relay");
+        let busy = StressData { switching_rate: Some(10.0), current_ratio: Some(0.5), ..Default::default() };
+        assert!(close(fit(&CalcContext::new(&relay, &prof, Some(&var)).with_stress(busy)), 20.0));
+        let idle = StressData { switching_rate: Some(0.2), ..Default::default() };
+        assert!(close(fit(&CalcContext::new(&relay, &prof, Some(&var)).with_stress(idle)), 2.0));
+
+        // Inductor hot spot 10 K/W × 2 W above 40 °C: πT(Ea = 0.15 eV, 60 °C)
+        let inductor = component("inductor");
+        let heated = StressData { thermal_resistance: Some(10.0), power_dissipation: Some(2.0), ..Default::default() };
+        assert!(close(fit(&CalcContext::new(&inductor, &prof, Some(&var)).with_stress(heated)), 2.0 * 1.396577));
+    }
 }
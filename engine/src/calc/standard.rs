@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::errors::FmedaError;
+use crate::models::{Component, ComponentVariant, MissionProfile};
+use super::{sn29500::Sn29500, IEC61709::Iec61709, IEC62380::Iec62380};
+
+/// Electrical and thermal stress applied to a component
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StressData {
+    /// Applied-to-rated voltage U/Urat
+    pub voltage_ratio: Option<f64>,
+    /// Applied-to-rated current I/Irat
+    pub current_ratio: Option<f64>,
+    /// Dissipated power P (W)
+    pub power_dissipation: Option<f64>,
+    /// Junction / hot-spot to ambient thermal resistance Rth (K/W)
+    pub thermal_resistance: Option<f64>,
+    /// Operations per hour of relays and switches
+    pub switching_rate: Option<f64>,
+}
+
+impl StressData {
+    /// Stress data stored on the component row
+    pub fn from_component(component: &Component) -> Self {
+        Self {
+            voltage_ratio: component.voltage_ratio,
+            current_ratio: component.current_ratio,
+            power_dissipation: component.power_dissipation,
+            thermal_resistance: component.thermal_resistance,
+            switching_rate: component.switching_rate,
+        }
+    }
+}
+
+/// Common input handed to every reliability standard
+#[derive(Debug, Clone, Copy)]
+pub struct CalcContext<'a> {
+    pub component: &'a Component,
+    pub variant: Option<&'a ComponentVariant>,
+    pub profile: &'a MissionProfile,
+    pub stress: StressData,
+    /// Multiplier on the activation energies of the πT models (1 = handbook values)
+    pub ea_scale: f64,
+}
+
+impl<'a> CalcContext<'a> {
+    /// Context with the stress data taken from the component
+    pub fn new(
+        component: &'a Component,
+        profile: &'a MissionProfile,
+        variant: Option<&'a ComponentVariant>,
+    ) -> Self {
+        Self {
+            component,
+            variant,
+            profile,
+            stress: StressData::from_component(component),
+            ea_scale: 1.0,
+        }
+    }
+
+    /// Override the stress data (e.g. for what-if derating studies)
+    pub fn with_stress(mut self, stress: StressData) -> Self {
+        self.stress = stress;
+        self
+    }
+
+    /// Scale the activation energies (e.g. for uncertainty propagation)
+    pub fn with_ea_scale(mut self, ea_scale: f64) -> Self {
+        self.ea_scale = ea_scale;
+        self
+    }
+}
+
+/// A reliability prediction standard (handbook) producing a component FIT
+pub trait ReliabilityStandard: Send + Sync {
+    /// Key under which the standard is registered, e.g. "SN29500"
+    fn name(&self) -> &str;
+
+    /// FIT (failures per 1e9 h) of one component under the context
+    fn calc_fit(&self, ctx: &CalcContext) -> Result<f64, FmedaError>;
+
+    /// πT of the component at each mission-profile segment, in segment order.
+    /// `None` when the standard has no per-segment temperature factor.
+    fn segment_pi_t(&self, _ctx: &CalcContext) -> Option<Vec<f64>> {
+        None
+    }
+}
+
+/// Runtime registry of reliability standards, keyed by name
+#[derive(Default, Clone)]
+pub struct StandardRegistry {
+    standards: HashMap<String, Arc<dyn ReliabilityStandard>>,
+}
+
+impl StandardRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding the standards shipped with the engine
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(Sn29500);
+        registry.register(Iec61709);
+        registry.register(Iec62380);
+        registry
+    }
+
+    /// Register a standard under its name; returns the standard it replaced, if any
+    pub fn register<S: ReliabilityStandard + 'static>(
+        &mut self,
+        standard: S,
+    ) -> Option<Arc<dyn ReliabilityStandard>> {
+        self.standards.insert(standard.name().to_string(), Arc::new(standard))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn ReliabilityStandard> {
+        self.standards.get(name).map(|s| s.as_ref())
+    }
+
+    /// Registered standard names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.standards.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Calculate the FIT of the context with the named standard.
+    /// The mission profile is validated first.
+    pub fn calculate_fit(&self, name: &str, ctx: &CalcContext) -> Result<f64, FmedaError> {
+        let standard = self
+            .get(name)
+            .ok_or_else(|| FmedaError::UnknownStandard(name.to_string()))?;
+        ctx.profile.validate()?;
+        standard.calc_fit(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{component, profile, variant, ReferenceFit};
+
+    #[test]
+    fn builtin_registry_holds_the_shipped_standards() {
+        assert_eq!(StandardRegistry::builtin().names(), vec!["IEC61709", "IEC62380", "SN29500"]);
+    }
+
+    #[test]
+    fn custom_standard_is_looked_up_by_name() {
+        let mut registry = StandardRegistry::new();
+        assert!(registry.register(ReferenceFit).is_none());
+        assert!(registry.register(ReferenceFit).is_some());
+
+        let (comp, var, prof) = (component("resistor"), variant(7.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        assert_eq!(registry.calculate_fit("REFERENCE", &ctx).unwrap(), 7.0);
+        assert!(matches!(registry.calculate_fit("SN29500", &ctx), Err(FmedaError::UnknownStandard(_))));
+    }
+
+    #[test]
+    fn profile_is_validated_before_the_standard_runs() {
+        let mut registry = StandardRegistry::new();
+        registry.register(ReferenceFit);
+        let (comp, var, prof) = (component("resistor"), variant(7.0), profile(&[(40.0, 0.5)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        assert!(matches!(registry.calculate_fit("REFERENCE", &ctx), Err(FmedaError::TauNotNormalised { .. })));
+    }
+}
@@ -1,3 +1,6 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -8,6 +11,96 @@ pub enum FmedaError {
     #[error("Calculation error: {0}")]
     Calculation(String),
 
+    #[error("Unknown standard: {0}")]
+    UnknownStandard(String),
+
+    #[error("Component variant not set; cannot calculate {0} FIT")]
+    MissingVariant(String),
+
+    #[error("Mission profile has no segments")]
+    EmptyMissionProfile,
+
+    #[error("Mission profile segment {index}: negative τ ({tau})")]
+    NegativeTau { index: usize, tau: f64 },
+
+    #[error("Mission profile τ values sum to {sum}, expected 1 or 8760 h")]
+    TauNotNormalised { sum: f64 },
+
+    #[error("Mission profile segment {index}: temperature {temperature} °C is below absolute zero")]
+    TemperatureBelowAbsoluteZero { index: usize, temperature: f64 },
+
+    #[error("Mission profile segment {index}: temperature {temperature} °C is outside the supported range")]
+    TemperatureOutOfRange { index: usize, temperature: f64 },
+
+    #[error("Mission profile segment {index} duplicates segment {first}")]
+    DuplicateSegment { index: usize, first: usize },
+
+    #[error("Mission profile segment {index}: {reason}")]
+    InvalidSegment { index: usize, reason: String },
+
+    #[error("Invalid ASIL: {0}")]
+    InvalidAsil(String),
+
+    #[error("Invalid IEC 61508 element type: {0}")]
+    InvalidElementType(String),
+
+    #[error("Invalid voting architecture: {0}")]
+    InvalidArchitecture(String),
+
+    #[error("Failure-mode library {library}: shares of {selector} sum to {sum}, expected 1")]
+    DistributionSum { library: String, selector: String, sum: f64 },
+
+    #[error("No failure-mode distribution for component {0}")]
+    NoDistribution(String),
+
+    #[error("Failure-mode library {library}: mode '{mode}' has no matching failure mode for component {component}")]
+    UnmatchedLibraryMode { library: String, mode: String, component: String },
+
+    #[error("Failure-mode library {library}: no share for failure mode '{mode}' of component {component}")]
+    UnmappedFailureMode { library: String, mode: String, component: String },
+
+    #[error("Invalid fault tree: {0}")]
+    InvalidFaultTree(String),
+
+    #[error("Fault tree basic event '{event}': {reason}")]
+    UnresolvedBasicEvent { event: String, reason: String },
+
+    #[error("Invalid reliability block diagram: {0}")]
+    InvalidBlockDiagram(String),
+
+    #[error("Invalid Markov model: {0}")]
+    InvalidMarkovModel(String),
+
+    #[error("Invalid uncertainty distribution: {0}")]
+    InvalidDistribution(String),
+
+    #[error("Invalid sensitivity parameter: {0}")]
+    InvalidSensitivityParameter(String),
+
+    #[error("Invalid field data: {0}")]
+    InvalidFieldData(String),
+
+    #[error("Invalid Weibull parameters: β = {beta}, η = {eta} h")]
+    InvalidWeibull { beta: f64, eta: f64 },
+
     #[error("Other error: {0}")]
     Other(String),
 }
+
+impl FmedaError {
+    /// HTTP status reported for the error
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            FmedaError::Database(_) | FmedaError::Other(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            FmedaError::Calculation(_) | FmedaError::MissingVariant(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl IntoResponse for FmedaError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "error": self.to_string() });
+        (self.status_code(), Json(body)).into_response()
+    }
+}
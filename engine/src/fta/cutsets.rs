@@ -0,0 +1,160 @@
+use itertools::Itertools;
+use std::collections::BTreeSet;
+
+use crate::errors::FmedaError;
+use super::{FaultTreeDef, GateKind};
+
+/// Set of basic event ids whose joint occurrence causes the top event
+pub type CutSet = BTreeSet<String>;
+
+/// Minimal cut sets of the tree by MOCUS (top-down gate substitution).
+///
+/// Rows hold gates and events; every gate in a row is replaced by its inputs:
+/// AND and INHIBIT extend the row, OR splits it into one row per input and
+/// k-of-n into one row per combination of k inputs. Cut sets above
+/// `max_order` events are dropped.
+pub fn minimal_cut_sets(tree: &FaultTreeDef, max_order: Option<usize>) -> Result<Vec<CutSet>, FmedaError> {
+    tree.validate()?;
+
+    let mut rows: Vec<Vec<String>> = vec![vec![tree.top.clone()]];
+    let mut cut_sets: Vec<CutSet> = Vec::new();
+
+    while let Some(row) = rows.pop() {
+        let gate = row.iter().enumerate().find_map(|(i, id)| Some((i, tree.gate(id)?)));
+
+        let Some((index, gate)) = gate else {
+            let cut_set: CutSet = row.into_iter().collect();
+            if max_order.is_none_or(|max| cut_set.len() <= max) {
+                cut_sets.push(cut_set);
+            }
+            continue;
+        };
+
+        let mut rest = row;
+        rest.swap_remove(index);
+
+        let expansions: Vec<Vec<String>> = match gate.kind {
+            GateKind::And | GateKind::Inhibit { .. } => vec![gate.inputs.clone()],
+            GateKind::Or => gate.inputs.iter().map(|i| vec![i.clone()]).collect(),
+            GateKind::KOfN { k } => gate.inputs.iter().cloned().combinations(k).collect(),
+        };
+
+        for expansion in expansions {
+            let mut new_row = rest.clone();
+            for id in expansion {
+                if !new_row.contains(&id) {
+                    new_row.push(id);
+                }
+            }
+            rows.push(new_row);
+        }
+    }
+
+    Ok(minimise(cut_sets))
+}
+
+/// Remove duplicates and every cut set that contains another one
+fn minimise(mut cut_sets: Vec<CutSet>) -> Vec<CutSet> {
+    cut_sets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    cut_sets.dedup();
+
+    let mut minimal: Vec<CutSet> = Vec::new();
+    for cut_set in cut_sets {
+        if !minimal.iter().any(|m| m.is_subset(&cut_set)) {
+            minimal.push(cut_set);
+        }
+    }
+    minimal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fta::{BasicEvent, Gate};
+
+    fn gate(id: &str, kind: GateKind, inputs: &[&str]) -> Gate {
+        Gate {
+            id: id.to_string(),
+            name: None,
+            kind,
+            inputs: inputs.iter().map(|i| i.to_string()).collect(),
+        }
+    }
+
+    fn tree(gates: Vec<Gate>, events: &[&str]) -> FaultTreeDef {
+        FaultTreeDef {
+            top: gates[0].id.clone(),
+            gates,
+            events: events
+                .iter()
+                .map(|id| BasicEvent {
+                    id: id.to_string(),
+                    name: None,
+                    component_id: None,
+                    failure_mode_id: None,
+                    fit: Some(1.0),
+                    probability: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn sets(expected: &[&[&str]]) -> Vec<CutSet> {
+        expected.iter().map(|s| s.iter().map(|id| id.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn mocus_expands_and_or() {
+        // TOP = A·(B + C) + B
+        let t = tree(
+            vec![
+                gate("top", GateKind::Or, &["g1", "b"]),
+                gate("g1", GateKind::And, &["a", "g2"]),
+                gate("g2", GateKind::Or, &["b", "c"]),
+            ],
+            &["a", "b", "c"],
+        );
+        // {a, b} is absorbed by {b}
+        assert_eq!(minimal_cut_sets(&t, None).unwrap(), sets(&[&["b"], &["a", "c"]]));
+    }
+
+    #[test]
+    fn mocus_shared_event_and_k_of_n() {
+        // 2-of-3 voting: every pair of channels
+        let t = tree(vec![gate("top", GateKind::KOfN { k: 2 }, &["a", "b", "c"])], &["a", "b", "c"]);
+        assert_eq!(minimal_cut_sets(&t, None).unwrap(), sets(&[&["a", "b"], &["a", "c"], &["b", "c"]]));
+
+        // (A + B)·(A + C) = A + B·C
+        let t = tree(
+            vec![
+                gate("top", GateKind::And, &["g1", "g2"]),
+                gate("g1", GateKind::Or, &["a", "b"]),
+                gate("g2", GateKind::Or, &["a", "c"]),
+            ],
+            &["a", "b", "c"],
+        );
+        assert_eq!(minimal_cut_sets(&t, None).unwrap(), sets(&[&["a"], &["b", "c"]]));
+        assert_eq!(minimal_cut_sets(&t, Some(1)).unwrap(), sets(&[&["a"]]));
+    }
+
+    #[test]
+    fn inhibit_condition_must_be_a_basic_event() {
+        let t = tree(
+            vec![
+                gate("top", GateKind::Inhibit { condition: 1 }, &["a", "g1"]),
+                gate("g1", GateKind::Or, &["b", "c"]),
+            ],
+            &["a", "b", "c"],
+        );
+        assert!(matches!(minimal_cut_sets(&t, None), Err(FmedaError::InvalidFaultTree(_))));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let t = tree(
+            vec![gate("top", GateKind::Or, &["g1", "a"]), gate("g1", GateKind::And, &["top", "a"])],
+            &["a"],
+        );
+        assert!(t.validate().is_err());
+    }
+}
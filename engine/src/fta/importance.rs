@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::errors::FmedaError;
+use super::cutsets::minimal_cut_sets;
+use super::quantify::{min_cut_upper_bound, EventData};
+use super::FaultTreeDef;
+
+/// Importance of one basic event for the top event
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EventImportance {
+    pub event_id: String,
+    pub component_id: Option<Uuid>,
+    pub failure_mode_id: Option<Uuid>,
+    /// The event is an INHIBIT condition (enabling state) rather than a failure
+    pub condition: bool,
+    pub probability: f64,
+    /// Birnbaum: P(top | q = 1) − P(top | q = 0)
+    pub birnbaum: f64,
+    /// Fussell–Vesely: [P(top) − P(top | q = 0)] / P(top)
+    pub fussell_vesely: f64,
+    /// Risk Achievement Worth: P(top | q = 1) / P(top)
+    pub raw: f64,
+    /// Risk Reduction Worth: P(top) / P(top | q = 0); `None` when the event is in every cut set
+    pub rrw: Option<f64>,
+}
+
+/// Importance measures of every basic event in a minimal cut set,
+/// sorted by decreasing Fussell–Vesely importance
+pub fn importance_measures(
+    tree: &FaultTreeDef,
+    events: &HashMap<String, EventData>,
+    mission_time_h: f64,
+    max_order: Option<usize>,
+) -> Result<Vec<EventImportance>, FmedaError> {
+    let cut_sets = minimal_cut_sets(tree, max_order)?;
+
+    let mut q: HashMap<&str, f64> = HashMap::new();
+    for id in cut_sets.iter().flatten() {
+        let data = events
+            .get(id)
+            .ok_or_else(|| FmedaError::InvalidFaultTree(format!("no data for basic event '{}'", id)))?;
+        q.insert(id.as_str(), data.probability(mission_time_h));
+    }
+
+    let conditions = tree.conditions();
+    let top = min_cut_upper_bound(&cut_sets, |id| q[id]);
+    let top_with = |event: &str, value: f64| {
+        min_cut_upper_bound(&cut_sets, |id| if id == event { value } else { q[id] })
+    };
+
+    let mut measures: Vec<EventImportance> = tree
+        .events
+        .iter()
+        .filter(|e| q.contains_key(e.id.as_str()))
+        .map(|event| {
+            let failed = top_with(&event.id, 1.0);
+            let perfect = top_with(&event.id, 0.0);
+            let ratio = |num: f64, den: f64| if den > 0.0 { num / den } else { 0.0 };
+
+            EventImportance {
+                event_id: event.id.clone(),
+                component_id: event.component_id,
+                failure_mode_id: event.failure_mode_id,
+                condition: conditions.contains(event.id.as_str()),
+                probability: q[event.id.as_str()],
+                birnbaum: failed - perfect,
+                fussell_vesely: ratio(top - perfect, top),
+                raw: ratio(failed, top),
+                rrw: (perfect > 0.0).then(|| top / perfect),
+            }
+        })
+        .collect();
+
+    measures.sort_by(|a, b| b.fussell_vesely.total_cmp(&a.fussell_vesely));
+    Ok(measures)
+}
@@ -0,0 +1,299 @@
+pub mod cutsets;
+pub mod importance;
+pub mod quantify;
+
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::errors::FmedaError;
+use crate::models::FMEDAPredictionResult;
+
+pub use cutsets::{minimal_cut_sets, CutSet};
+pub use importance::{importance_measures, EventImportance};
+pub use quantify::{quantify, EventData, FtaResult};
+
+/// Gate logic of a fault tree
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GateKind {
+    And,
+    Or,
+    /// Output occurs when at least `k` of the inputs occur
+    KOfN { k: usize },
+    /// Output occurs when the single input occurs while the condition is present
+    Inhibit { condition: usize },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Gate {
+    pub id: String,
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub kind: GateKind,
+    /// Ids of gates or basic events
+    pub inputs: Vec<String>,
+}
+
+/// Basic event of the tree. Its rate comes from the FMEDA failure mode it
+/// refers to; `fit` and `probability` are only used for events without one
+/// (e.g. external events and INHIBIT conditions).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BasicEvent {
+    pub id: String,
+    pub name: Option<String>,
+    pub component_id: Option<Uuid>,
+    pub failure_mode_id: Option<Uuid>,
+    pub fit: Option<f64>,
+    /// Fixed probability, for conditions and demands
+    pub probability: Option<f64>,
+}
+
+/// Fault tree as stored in `fault_trees.tree`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FaultTreeDef {
+    /// Id of the top-event gate
+    pub top: String,
+    pub gates: Vec<Gate>,
+    pub events: Vec<BasicEvent>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct FaultTree {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub tree: Json<FaultTreeDef>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FaultTree {
+    pub async fn fetch(pool: &PgPool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as::<_, FaultTree>("SELECT * FROM fault_trees WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+impl FaultTreeDef {
+    pub fn gate(&self, id: &str) -> Option<&Gate> {
+        self.gates.iter().find(|g| g.id == id)
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.gate(id).is_some() || self.events.iter().any(|e| e.id == id)
+    }
+
+    /// Check ids, gate arity and that the gates form no cycle
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        let invalid = |msg: String| Err(FmedaError::InvalidFaultTree(msg));
+
+        let mut ids: Vec<&str> = self.gates.iter().map(|g| g.id.as_str()).collect();
+        ids.extend(self.events.iter().map(|e| e.id.as_str()));
+        ids.sort_unstable();
+        if let Some(w) = ids.windows(2).find(|w| w[0] == w[1]) {
+            return invalid(format!("duplicate id '{}'", w[0]));
+        }
+
+        if !self.gates.iter().any(|g| g.id == self.top) {
+            return invalid(format!("top gate '{}' not found", self.top));
+        }
+
+        for gate in &self.gates {
+            if let Some(input) = gate.inputs.iter().find(|i| !self.contains(i)) {
+                return invalid(format!("gate '{}': unknown input '{}'", gate.id, input));
+            }
+            match gate.kind {
+                _ if gate.inputs.is_empty() => return invalid(format!("gate '{}' has no inputs", gate.id)),
+                GateKind::KOfN { k } if k == 0 || k > gate.inputs.len() => {
+                    return invalid(format!("gate '{}': k = {} with {} inputs", gate.id, k, gate.inputs.len()));
+                }
+                GateKind::Inhibit { .. } if gate.inputs.len() != 2 => {
+                    return invalid(format!("INHIBIT gate '{}' needs an input and a condition", gate.id));
+                }
+                GateKind::Inhibit { condition } if condition > 1 => {
+                    return invalid(format!("INHIBIT gate '{}': condition index {} out of range", gate.id, condition));
+                }
+                GateKind::Inhibit { condition } if self.gate(&gate.inputs[condition]).is_some() => {
+                    return invalid(format!("INHIBIT gate '{}': condition must be a basic event", gate.id));
+                }
+                _ => {}
+            }
+        }
+
+        // Depth-first search for cycles: 1 = on the stack, 2 = done
+        let mut state: HashMap<&str, u8> = HashMap::new();
+        fn visit<'a>(tree: &'a FaultTreeDef, id: &'a str, state: &mut HashMap<&'a str, u8>) -> Result<(), FmedaError> {
+            match state.get(id) {
+                Some(1) => return Err(FmedaError::InvalidFaultTree(format!("cycle through gate '{}'", id))),
+                Some(_) => return Ok(()),
+                None => {}
+            }
+            if let Some(gate) = tree.gate(id) {
+                state.insert(id, 1);
+                for input in &gate.inputs {
+                    visit(tree, input, state)?;
+                }
+            }
+            state.insert(id, 2);
+            Ok(())
+        }
+        for gate in &self.gates {
+            visit(self, &gate.id, &mut state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Basic events used as INHIBIT conditions: enabling states rather than failures
+    pub fn conditions(&self) -> HashSet<&str> {
+        self.gates
+            .iter()
+            .filter_map(|g| match g.kind {
+                GateKind::Inhibit { condition } => g.inputs.get(condition).map(String::as_str),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Rates and probabilities of the basic events. Events referring to an
+    /// FMEDA failure mode take its FIT from the component prediction; a
+    /// hand-entered `fit` or `probability` on such an event is ignored.
+    pub fn event_data(&self, predictions: &[FMEDAPredictionResult]) -> Result<HashMap<String, EventData>, FmedaError> {
+        self.events
+            .iter()
+            .map(|event| {
+                let data = match (event.failure_mode_id, event.fit, event.probability) {
+                    (Some(fm_id), fit, probability) => {
+                        if fit.is_some() || probability.is_some() {
+                            report!(
+                                "[WARN] Basic event '{}': FIT/probability ignored; rate comes from the FMEDA failure mode",
+                                event.id
+                            );
+                        }
+                        EventData::Rate { fit: fmeda_fit(event, fm_id, predictions)? }
+                    }
+                    (None, _, Some(p)) => EventData::Probability(p),
+                    (None, Some(fit), None) => EventData::Rate { fit },
+                    (None, None, None) => {
+                        return Err(FmedaError::InvalidFaultTree(format!(
+                            "basic event '{}' has no FMEDA failure mode, FIT or probability",
+                            event.id
+                        )))
+                    }
+                };
+                Ok((event.id.clone(), data))
+            })
+            .collect()
+    }
+}
+
+/// FIT of the failure mode a basic event refers to. Without a `component_id`
+/// the failure mode must occur in exactly one component prediction.
+fn fmeda_fit(event: &BasicEvent, fm_id: Uuid, predictions: &[FMEDAPredictionResult]) -> Result<f64, FmedaError> {
+    let unresolved = |reason: String| Err(FmedaError::UnresolvedBasicEvent { event: event.id.clone(), reason });
+
+    let candidates: Vec<&FMEDAPredictionResult> = match event.component_id {
+        Some(c) => {
+            let found: Vec<_> = predictions.iter().filter(|p| p.component_id == c).collect();
+            if found.is_empty() {
+                return unresolved(format!("component {} has no FMEDA prediction", c));
+            }
+            found
+        }
+        None => predictions.iter().collect(),
+    };
+
+    let matches: Vec<f64> = candidates
+        .iter()
+        .flat_map(|p| &p.failure_modes)
+        .filter(|fm| fm.failure_mode_id == fm_id)
+        .map(|fm| fm.fit)
+        .collect();
+
+    match matches.as_slice() {
+        [] => unresolved(format!("failure mode {} is not in the FMEDA predictions", fm_id)),
+        [fit] => Ok(*fit),
+        _ => unresolved(format!("failure mode {} occurs in several predictions; set component_id", fm_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FailureModeFit;
+
+    fn prediction(component_id: Uuid, failure_mode_id: Uuid, fit: f64) -> FMEDAPredictionResult {
+        FMEDAPredictionResult {
+            component_id,
+            standard: "TEST".to_string(),
+            weighted_pi_t: None,
+            segments: Vec::new(),
+            total_fit: fit,
+            failure_modes: vec![FailureModeFit { failure_mode_id, mode: "open".to_string(), share: 1.0, fit }],
+            distribution: None,
+        }
+    }
+
+    fn tree(event: BasicEvent) -> FaultTreeDef {
+        FaultTreeDef {
+            top: "top".to_string(),
+            gates: vec![Gate { id: "top".to_string(), name: None, kind: GateKind::Or, inputs: vec![event.id.clone()] }],
+            events: vec![event],
+        }
+    }
+
+    fn event(component_id: Option<Uuid>, failure_mode_id: Option<Uuid>) -> BasicEvent {
+        BasicEvent {
+            id: "e".to_string(),
+            name: None,
+            component_id,
+            failure_mode_id,
+            fit: Some(1.0),
+            probability: Some(0.5),
+        }
+    }
+
+    fn fit_of(data: &HashMap<String, EventData>) -> f64 {
+        match data["e"] {
+            EventData::Rate { fit } => fit,
+            EventData::Probability(_) => panic!("expected a rate"),
+        }
+    }
+
+    #[test]
+    fn fmeda_fit_takes_priority_over_entered_values() {
+        let (c, fm) = (Uuid::new_v4(), Uuid::new_v4());
+        let data = tree(event(Some(c), Some(fm))).event_data(&[prediction(c, fm, 42.0)]).unwrap();
+        assert_eq!(fit_of(&data), 42.0);
+
+        let standalone = tree(event(None, None)).event_data(&[]).unwrap();
+        assert!(matches!(standalone["e"], EventData::Probability(p) if p == 0.5));
+    }
+
+    #[test]
+    fn missing_component_or_failure_mode_is_an_error() {
+        let (c, fm) = (Uuid::new_v4(), Uuid::new_v4());
+        let predictions = [prediction(c, fm, 42.0)];
+        let unresolved = |e: BasicEvent| {
+            matches!(tree(e).event_data(&predictions), Err(FmedaError::UnresolvedBasicEvent { .. }))
+        };
+        assert!(unresolved(event(Some(Uuid::new_v4()), Some(fm))));
+        assert!(unresolved(event(Some(c), Some(Uuid::new_v4()))));
+    }
+
+    #[test]
+    fn shared_failure_mode_needs_a_component() {
+        let (a, b, fm) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let predictions = [prediction(a, fm, 10.0), prediction(b, fm, 20.0)];
+        assert!(matches!(
+            tree(event(None, Some(fm))).event_data(&predictions),
+            Err(FmedaError::UnresolvedBasicEvent { .. })
+        ));
+        assert_eq!(fit_of(&tree(event(Some(b), Some(fm))).event_data(&predictions).unwrap()), 20.0);
+    }
+}
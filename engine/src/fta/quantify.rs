@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::errors::FmedaError;
+use super::cutsets::{minimal_cut_sets, CutSet};
+use super::FaultTreeDef;
+
+/// Reliability data of a basic event
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub enum EventData {
+    /// Non-repairable failure with constant rate (FIT)
+    Rate { fit: f64 },
+    /// Fixed probability (conditions, demands)
+    Probability(f64),
+}
+
+impl EventData {
+    /// Probability of occurrence within the mission time: q = 1 − e^(−λ·t)
+    pub fn probability(&self, mission_time_h: f64) -> f64 {
+        match *self {
+            EventData::Rate { fit } => 1.0 - (-fit * FIT_TO_PER_HOUR * mission_time_h).exp(),
+            EventData::Probability(p) => p,
+        }
+    }
+
+    /// Failure rate (1/h); zero for fixed-probability events
+    pub fn rate(&self) -> f64 {
+        match *self {
+            EventData::Rate { fit } => fit * FIT_TO_PER_HOUR,
+            EventData::Probability(_) => 0.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CutSetResult {
+    pub events: CutSet,
+    /// INHIBIT conditions in the cut set; they enable it but do not initiate it
+    pub conditions: Vec<String>,
+    pub probability: f64,
+    /// Occurrence frequency of the cut set (1/h)
+    pub frequency: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FtaResult {
+    pub mission_time_h: f64,
+    /// Minimal cut sets sorted by decreasing probability
+    pub cut_sets: Vec<CutSetResult>,
+    /// Rare-event approximation ΣP(MCS)
+    pub top_probability_rare_event: f64,
+    /// Min-cut upper bound 1 − Π(1 − P(MCS))
+    pub top_probability: f64,
+    /// Top-event frequency Σ_MCS Σ_i λi·Π_(j≠i) qj over the initiating events i (1/h)
+    pub top_frequency: f64,
+    /// Top-event frequency in FIT
+    pub top_fit: f64,
+}
+
+/// Minimal cut sets and top-event probability and frequency over the mission time
+pub fn quantify(
+    tree: &FaultTreeDef,
+    events: &HashMap<String, EventData>,
+    mission_time_h: f64,
+    max_order: Option<usize>,
+) -> Result<FtaResult, FmedaError> {
+    if mission_time_h <= 0.0 {
+        return Err(FmedaError::Calculation("mission time must be positive".to_string()));
+    }
+
+    let data = |id: &str| {
+        events
+            .get(id)
+            .copied()
+            .ok_or_else(|| FmedaError::InvalidFaultTree(format!("no data for basic event '{}'", id)))
+    };
+
+    let conditions = tree.conditions();
+    let mut cut_sets = Vec::new();
+    for cut_set in minimal_cut_sets(tree, max_order)? {
+        let members: Vec<EventData> = cut_set.iter().map(|id| data(id)).collect::<Result<_, _>>()?;
+        let q: Vec<f64> = members.iter().map(|d| d.probability(mission_time_h)).collect();
+
+        let probability: f64 = q.iter().product();
+        // INHIBIT conditions are states that must already be present, so only
+        // the other events can initiate the cut set
+        let frequency: f64 = cut_set
+            .iter()
+            .zip(&members)
+            .enumerate()
+            .filter(|(_, (id, _))| !conditions.contains(id.as_str()))
+            .map(|(i, (_, d))| {
+                let others: f64 = q.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, q)| q).product();
+                d.rate() * others
+            })
+            .sum();
+        let cut_set_conditions = cut_set
+            .iter()
+            .filter(|id| conditions.contains(id.as_str()))
+            .cloned()
+            .collect();
+
+        cut_sets.push(CutSetResult { events: cut_set, conditions: cut_set_conditions, probability, frequency });
+    }
+    cut_sets.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+
+    let top_probability_rare_event = cut_sets.iter().map(|c| c.probability).sum();
+    let top_probability = 1.0 - cut_sets.iter().map(|c| 1.0 - c.probability).product::<f64>();
+    let top_frequency: f64 = cut_sets.iter().map(|c| c.frequency).sum();
+
+    report!(
+        "[FTA] {} | {} minimal cut sets | P(top) = {:.6e} | w(top) = {:.6e} /h",
+        tree.top,
+        cut_sets.len(),
+        top_probability,
+        top_frequency
+    );
+
+    Ok(FtaResult {
+        mission_time_h,
+        cut_sets,
+        top_probability_rare_event,
+        top_probability,
+        top_frequency,
+        top_fit: top_frequency / FIT_TO_PER_HOUR,
+    })
+}
+
+/// Min-cut upper bound of the top-event probability for the event probabilities `q`
+pub(crate) fn min_cut_upper_bound(cut_sets: &[CutSet], q: impl Fn(&str) -> f64) -> f64 {
+    1.0 - cut_sets
+        .iter()
+        .map(|c| 1.0 - c.iter().map(|id| q(id)).product::<f64>())
+        .product::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fta::{BasicEvent, Gate, GateKind};
+
+    fn event(id: &str) -> BasicEvent {
+        BasicEvent {
+            id: id.to_string(),
+            name: None,
+            component_id: None,
+            failure_mode_id: None,
+            fit: None,
+            probability: None,
+        }
+    }
+
+    #[test]
+    fn inhibit_condition_enables_but_does_not_initiate() {
+        // TOP = fault INHIBIT demand, with a demand that is itself rate-based
+        let tree = FaultTreeDef {
+            top: "top".to_string(),
+            gates: vec![Gate {
+                id: "top".to_string(),
+                name: None,
+                kind: GateKind::Inhibit { condition: 1 },
+                inputs: vec!["fault".to_string(), "demand".to_string()],
+            }],
+            events: vec![event("fault"), event("demand")],
+        };
+        let events = HashMap::from([
+            ("fault".to_string(), EventData::Rate { fit: 1000.0 }),
+            ("demand".to_string(), EventData::Rate { fit: 1e6 }),
+        ]);
+
+        let result = quantify(&tree, &events, 1000.0, None).unwrap();
+        let cut_set = &result.cut_sets[0];
+        assert_eq!(cut_set.conditions, vec!["demand".to_string()]);
+
+        // Only the fault initiates: w = λ_fault·q_demand
+        let q_demand = 1.0 - (-1e6 * FIT_TO_PER_HOUR * 1000.0_f64).exp();
+        assert!((cut_set.frequency - 1000.0 * FIT_TO_PER_HOUR * q_demand).abs() < 1e-15);
+    }
+
+    #[test]
+    fn rare_event_and_upper_bound() {
+        let tree = FaultTreeDef {
+            top: "top".to_string(),
+            gates: vec![Gate {
+                id: "top".to_string(),
+                name: None,
+                kind: GateKind::Or,
+                inputs: vec!["a".to_string(), "b".to_string()],
+            }],
+            events: vec![event("a"), event("b")],
+        };
+        let events = HashMap::from([
+            ("a".to_string(), EventData::Probability(0.1)),
+            ("b".to_string(), EventData::Probability(0.2)),
+        ]);
+
+        let result = quantify(&tree, &events, 1.0, None).unwrap();
+        assert!((result.top_probability_rare_event - 0.3).abs() < 1e-12);
+        assert!((result.top_probability - 0.28).abs() < 1e-12);
+    }
+}
@@ -1,2 +1,15 @@
+#[macro_use]
+mod report;
+
 pub mod models;
 pub mod db;
+pub mod calc;
+pub mod errors;
+pub mod routes;
+pub mod safety;
+pub mod fta;
+pub mod reliability;
+pub mod uncertainty;
+
+#[cfg(test)]
+mod test_support;
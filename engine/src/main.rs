@@ -69,20 +69,20 @@
 //     println!("\n✅ FMEDA FIT computation complete.");
 //     Ok(())
 // }
-mod db;
-mod models;
-mod calc;
-
-use db::init_db;
-use models::{Component, ComponentVariant, MissionProfile};
+use fmeda_engine::db::init_db;
+use fmeda_engine::models::{Component, ComponentVariant, MissionProfile};
 use std::error::Error;
 use std::io::{self, Write};
 use itertools::Itertools; // Add to Cargo.toml: itertools = "0.10"
-use crate::calc::calculate_fit;
+use fmeda_engine::calc::{calculate_fit, StandardRegistry};
 use uuid::Uuid;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    // Reliability standard from the first CLI argument (default SN29500)
+    let standard = std::env::args().nth(1).unwrap_or_else(|| "SN29500".to_string());
+    let registry = StandardRegistry::builtin();
+
     // --- 1️⃣ Connect to DB ---
     let pool = init_db().await?;
     println!("[DB TEST] ✅ Connected to database.\n");
@@ -106,6 +106,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
     println!("Description: {}", profile.description.as_deref().unwrap_or("None"));
     println!("Temp/Tau Profile: {:#?}\n", profile.temp_tau_profile);
 
+    if let Err(e) = profile.validate() {
+        println!("❌ Invalid mission profile '{}': {}", profile.name, e);
+        return Err(e.into());
+    }
+
     // --- 3️⃣ Fetch all components with optional variants/subtypes ---
     let components: Vec<Component> = sqlx::query_as::<_, Component>(
         "SELECT * FROM components"
@@ -217,10 +222,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .fetch_one(&pool)
                 .await?;
 
-                if selected_subtypes.contains(&var.subtype_id) {
-                    if !variants.iter().any(|(n, _, _)| n == &var.name) {
-                        variants.push((var.name.clone(), var.id, var.subtype_id));
-                    }
+                if selected_subtypes.contains(&var.subtype_id)
+                    && !variants.iter().any(|(n, _, _)| n == &var.name)
+                {
+                    variants.push((var.name.clone(), var.id, var.subtype_id));
                 }
             }
         }
@@ -257,7 +262,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     // --- 7️⃣ Compute FIT for matching components ---
-    println!("\nFIT Results:");
+    println!("\nFIT Results ({}):", standard);
     println!("{:<20} | {:<10} | {:<15} | {:<12} | {:<10}",
         "Manufacturer P/N", "Type", "Subtype", "Variant", "FIT");
 
@@ -279,7 +284,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     .fetch_one(&pool)
                     .await?;
 
-                    let fit = calculate_fit("SN29500", c, profile, Some(&variant))?;
+                    let fit = calculate_fit(&registry, &standard, c, profile, Some(&variant))?;
 
                     println!("{:<20} | {:<10} | {:<15} | {:<12} | {:<10.6}",
                         c.manufacturer_part_number,
@@ -162,9 +162,11 @@ use serde::{Serialize, Deserialize};
 use uuid::Uuid;
 use bigdecimal::{BigDecimal, ToPrimitive};
 use serde_json::Value;
-use std::collections::HashMap;
 use chrono::{DateTime, Utc}; // ✅ Changed from DateTime<Utc>
+use sqlx::types::Json;
 use sqlx::PgPool;
+use crate::calc::{CalcContext, ReliabilityStandard};
+use crate::errors::FmedaError;
 
 // ------------------- Structs -------------------
 
@@ -174,6 +176,13 @@ pub struct Project {
     pub name: String,
     pub description: Option<String>,
     pub created_at: DateTime<Utc>, // updated
+
+    // ISO 26262 ASIL of the project, e.g. "B" or "ASIL D" (migration 0010)
+    pub asil_level: Option<String>,
+
+    // PMHF inputs: vehicle operating lifetime and multiple-point fault detection interval, hours (migration 0011)
+    pub vehicle_lifetime_hours: Option<f64>,
+    pub mpf_detection_interval_hours: Option<f64>,
 }
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug)]
@@ -192,9 +201,33 @@ pub struct Component {
     pub resistor_type: Option<String>,
     pub mission_profile_id: Option<Uuid>,
     pub variant_id: Option<Uuid>,
+
+    // Electrical stress data (migration 0005)
+    pub capacitor_type: Option<String>,
+    pub voltage_ratio: Option<f64>,
+    pub current_ratio: Option<f64>,
+
+    // IEC 62380 die / package / EOS data (migration 0006)
+    pub technology: Option<String>,
+    pub transistor_count: Option<i64>,
+    pub manufacture_year: Option<i32>,
+    pub package_type: Option<String>,
+    pub pin_count: Option<i32>,
+    pub power_dissipation: Option<f64>,
+    pub thermal_resistance: Option<f64>,
+    pub interface_category: Option<String>,
+
+    // SN 29500-3 discrete semiconductor type (migration 0007)
+    pub semiconductor_type: Option<String>,
+
+    // Relay / switch operating rate in operations per hour (migration 0008)
+    pub switching_rate: Option<f64>,
+
+    // Hardware block the component belongs to (migration 0014)
+    pub hardware_block_id: Option<Uuid>,
 }
 
-#[derive(sqlx::FromRow, Serialize, Deserialize, Debug)]
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct ComponentVariant {
     pub id: Uuid,
     pub subtype_id: Uuid,
@@ -203,6 +236,23 @@ pub struct ComponentVariant {
     pub ref_temp: Option<f64>,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>, // updated
+
+    // Field-justified FIT and the field data it was derived from (migration 0017)
+    pub field_fit: Option<f64>,
+    pub field_data_id: Option<Uuid>,
+
+    // Weibull wear-out shape β and characteristic life η in hours (migration 0018);
+    // only the lifetime analysis reads these, predict_fmeda and the RBD use the FIT
+    pub weibull_beta: Option<f64>,
+    pub weibull_eta_hours: Option<f64>,
+}
+
+impl ComponentVariant {
+    /// Reference FIT the standards scale: the field-justified FIT when one
+    /// is recorded, else the handbook `ref_fit`
+    pub fn reference_fit(&self) -> f64 {
+        self.field_fit.unwrap_or(self.ref_fit)
+    }
 }
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
@@ -210,27 +260,200 @@ pub struct MissionProfile {
     pub id: Uuid,
     pub name: String,
     pub description: Option<String>,
-    pub temp_tau_profile: serde_json::Value,
+    pub temp_tau_profile: Json<TempTauProfile>,
     pub created_at: DateTime<Utc>, // updated
 
     pub reference_temp: Option<f64>,
     pub operating_temp: Option<f64>,
 }
 
-impl MissionProfile {
-    pub fn parse_temp_tau_profile(&self) -> Vec<(f64, f64)> {
-        if let Value::Array(items) = &self.temp_tau_profile {
-            items
-                .iter()
-                .filter_map(|v| {
-                    let t = v.get("temp")?.as_f64()?;
-                    let tau = v.get("tau")?.as_f64()?;
-                    Some((t, tau))
-                })
-                .collect()
-        } else {
-            vec![]
+/// Current version of the `temp_tau_profile` schema
+pub const MISSION_PROFILE_VERSION: u32 = 2;
+
+/// Accepted τ totals: fractions of the mission, or hours per year
+const TAU_TOTALS: [f64; 2] = [1.0, 8760.0];
+
+/// Relative tolerance on the τ total
+const TAU_TOLERANCE: f64 = 1e-3;
+
+/// Absolute zero (°C)
+const ABSOLUTE_ZERO: f64 = -273.15;
+
+/// Highest segment temperature accepted as physically sane (°C)
+const MAX_SEGMENT_TEMPERATURE: f64 = 300.0;
+
+/// One phase of a mission profile
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MissionSegment {
+    /// Ambient temperature (°C)
+    #[serde(alias = "temp")]
+    pub temperature: f64,
+    /// Time share of the phase (fraction of time, or hours per year)
+    pub tau: f64,
+    /// Relative humidity (%)
+    #[serde(default)]
+    pub humidity: Option<f64>,
+    /// Temperature swing of the thermal cycles in this phase (°C)
+    #[serde(default)]
+    pub delta_t: Option<f64>,
+    /// Thermal cycles per year in this phase
+    #[serde(default)]
+    pub cycles_per_year: Option<f64>,
+    /// Equipment powered (true) or dormant (false) during the phase
+    #[serde(default = "default_segment_on")]
+    pub on: bool,
+}
+
+fn default_segment_on() -> bool {
+    true
+}
+
+/// Typed, versioned content of `mission_profiles.temp_tau_profile`.
+///
+/// Stored as `{"version": 2, "segments": [...]}`. The legacy layouts
+/// `[{temp, tau}]`, `{segments: [{temperature, tau}]}` and
+/// `{temperature: [...], tau: [...]}` are upgraded on load.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "RawTempTauProfile")]
+pub struct TempTauProfile {
+    pub version: u32,
+    pub segments: Vec<MissionSegment>,
+}
+
+/// Every JSON layout `temp_tau_profile` has been stored in
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTempTauProfile {
+    Segments {
+        version: Option<u32>,
+        segments: Vec<MissionSegment>,
+    },
+    Parallel {
+        temperature: Vec<f64>,
+        tau: Vec<f64>,
+    },
+    Pairs(Vec<MissionSegment>),
+}
+
+impl TryFrom<RawTempTauProfile> for TempTauProfile {
+    type Error = String;
+
+    fn try_from(raw: RawTempTauProfile) -> Result<Self, Self::Error> {
+        let segments = match raw {
+            RawTempTauProfile::Segments { version: Some(v), .. } if v > MISSION_PROFILE_VERSION => {
+                return Err(format!("unsupported mission profile version {}", v));
+            }
+            RawTempTauProfile::Segments { segments, .. } => segments,
+            RawTempTauProfile::Parallel { temperature, tau } => {
+                if temperature.len() != tau.len() {
+                    return Err(format!(
+                        "temperature ({}) and tau ({}) arrays differ in length",
+                        temperature.len(),
+                        tau.len()
+                    ));
+                }
+                temperature
+                    .into_iter()
+                    .zip(tau)
+                    .map(|(temperature, tau)| MissionSegment {
+                        temperature,
+                        tau,
+                        humidity: None,
+                        delta_t: None,
+                        cycles_per_year: None,
+                        on: true,
+                    })
+                    .collect()
+            }
+            RawTempTauProfile::Pairs(segments) => segments,
+        };
+
+        Ok(TempTauProfile {
+            version: MISSION_PROFILE_VERSION,
+            segments,
+        })
+    }
+}
+
+impl TempTauProfile {
+    /// (temperature, τ) of every segment
+    pub fn temp_tau_pairs(&self) -> Vec<(f64, f64)> {
+        self.segments.iter().map(|s| (s.temperature, s.tau)).collect()
+    }
+
+    /// Check that the segments describe a usable mission profile
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        if self.segments.is_empty() {
+            return Err(FmedaError::EmptyMissionProfile);
         }
+
+        for (index, seg) in self.segments.iter().enumerate() {
+            seg.validate(index)?;
+            if let Some(first) = self.segments[..index].iter().position(|s| s.same_conditions(seg)) {
+                return Err(FmedaError::DuplicateSegment { index, first });
+            }
+        }
+
+        let sum: f64 = self.segments.iter().map(|s| s.tau).sum();
+        if !TAU_TOTALS.iter().any(|total| (sum - total).abs() <= total * TAU_TOLERANCE) {
+            return Err(FmedaError::TauNotNormalised { sum });
+        }
+
+        Ok(())
+    }
+}
+
+impl MissionSegment {
+    fn validate(&self, index: usize) -> Result<(), FmedaError> {
+        let invalid = |reason: &str| FmedaError::InvalidSegment { index, reason: reason.to_string() };
+
+        if !self.temperature.is_finite() || !self.tau.is_finite() {
+            return Err(invalid("temperature and τ must be finite"));
+        }
+        if self.tau < 0.0 {
+            return Err(FmedaError::NegativeTau { index, tau: self.tau });
+        }
+        if self.temperature < ABSOLUTE_ZERO {
+            return Err(FmedaError::TemperatureBelowAbsoluteZero { index, temperature: self.temperature });
+        }
+        if self.temperature > MAX_SEGMENT_TEMPERATURE {
+            return Err(FmedaError::TemperatureOutOfRange { index, temperature: self.temperature });
+        }
+        if self.humidity.is_some_and(|h| !(0.0..=100.0).contains(&h)) {
+            return Err(invalid("humidity must be within 0–100 %"));
+        }
+        if self.delta_t.is_some_and(|dt| dt < 0.0) {
+            return Err(invalid("ΔT must not be negative"));
+        }
+        if self.cycles_per_year.is_some_and(|n| n < 0.0) {
+            return Err(invalid("cycles per year must not be negative"));
+        }
+        if self.delta_t.is_some() != self.cycles_per_year.is_some() {
+            return Err(invalid("ΔT and cycles per year must be given together"));
+        }
+
+        Ok(())
+    }
+
+    /// Same operating conditions (everything but τ); such segments should be merged
+    fn same_conditions(&self, other: &MissionSegment) -> bool {
+        self.temperature == other.temperature
+            && self.humidity == other.humidity
+            && self.delta_t == other.delta_t
+            && self.cycles_per_year == other.cycles_per_year
+            && self.on == other.on
+    }
+}
+
+impl MissionProfile {
+    /// Mission-profile segments
+    pub fn segments(&self) -> &[MissionSegment] {
+        &self.temp_tau_profile.segments
+    }
+
+    /// Validate the temperature–τ segments of the profile
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        self.temp_tau_profile.validate()
     }
 
     pub async fn fetch(pool: &PgPool, id: Uuid) -> Result<Self, sqlx::Error> {
@@ -252,7 +475,7 @@ impl MissionProfile {
     }
 }
 
-#[derive(sqlx::FromRow, Serialize, Deserialize, Debug)]
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
 pub struct FailureMode {
     pub id: Uuid,
     pub mpn: Option<String>,
@@ -261,6 +484,166 @@ pub struct FailureMode {
     pub lambda: f64,
     pub detection_coverage: Option<f32>,
     pub created_at: DateTime<Utc>, // updated
+
+    // ISO 26262-5 FMEDA classification (migration 0010)
+    pub safe_fraction: Option<f32>,
+    #[serde(default = "default_violates_goal")]
+    pub violates_goal: bool,
+    pub latent_coverage: Option<f32>,
+}
+
+/// Failure modes violate the safety goal unless stated otherwise (matches the column default)
+fn default_violates_goal() -> bool {
+    true
+}
+
+/// Diagnostic kind of fault a coverage claim applies to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultPersistence {
+    Permanent,
+    Transient,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct SafetyMechanism {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub dc_permanent: f32,
+    pub dc_transient: Option<f32>,
+    /// ISO 26262-5 Annex D technique, e.g. "D.2.4.1"
+    pub annex_d_reference: Option<String>,
+    pub test_interval_hours: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Safety mechanism assigned to a failure mode
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct AssignedMechanism {
+    pub failure_mode_id: Uuid,
+    /// Mechanism detects latent multiple-point faults rather than single-point / residual faults
+    pub covers_latent: bool,
+    #[sqlx(flatten)]
+    pub mechanism: SafetyMechanism,
+}
+
+impl SafetyMechanism {
+    /// Claimed DC for the fault persistence; transient falls back to permanent
+    pub fn dc(&self, persistence: FaultPersistence) -> f32 {
+        match persistence {
+            FaultPersistence::Permanent => self.dc_permanent,
+            FaultPersistence::Transient => self.dc_transient.unwrap_or(self.dc_permanent),
+        }
+    }
+
+    pub async fn fetch(pool: &PgPool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as::<_, SafetyMechanism>("SELECT * FROM safety_mechanisms WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+    }
+
+    pub async fn fetch_all(pool: &PgPool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, SafetyMechanism>("SELECT * FROM safety_mechanisms ORDER BY name")
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Update the claimed DC; every failure mode the mechanism is assigned to picks it up
+    pub async fn update_coverage(
+        pool: &PgPool,
+        id: Uuid,
+        dc_permanent: f32,
+        dc_transient: Option<f32>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE safety_mechanisms SET dc_permanent = $2, dc_transient = $3 WHERE id = $1")
+            .bind(id)
+            .bind(dc_permanent)
+            .bind(dc_transient)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Assign the mechanism to a failure mode
+    pub async fn assign(
+        pool: &PgPool,
+        id: Uuid,
+        failure_mode_id: Uuid,
+        covers_latent: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO failure_mode_safety_mechanisms (failure_mode_id, safety_mechanism_id, covers_latent)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#
+        )
+        .bind(failure_mode_id)
+        .bind(id)
+        .bind(covers_latent)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+impl AssignedMechanism {
+    /// Mechanisms assigned to the given failure modes
+    pub async fn fetch_for_failure_modes(
+        pool: &PgPool,
+        failure_mode_ids: &[Uuid],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, AssignedMechanism>(
+            r#"
+            SELECT fsm.failure_mode_id, fsm.covers_latent, sm.*
+            FROM failure_mode_safety_mechanisms fsm
+            JOIN safety_mechanisms sm ON sm.id = fsm.safety_mechanism_id
+            WHERE fsm.failure_mode_id = ANY($1)
+            "#
+        )
+        .bind(failure_mode_ids)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+impl FailureMode {
+    /// Take detection and latent coverage from the assigned mechanisms.
+    ///
+    /// With several mechanisms on the same fault the highest claimed DC is
+    /// used, without crediting their combination. A coverage with no
+    /// assigned mechanism keeps the value stored on the row.
+    pub fn apply_mechanisms(&mut self, assigned: &[AssignedMechanism], persistence: FaultPersistence) {
+        let best = |latent: bool| {
+            assigned
+                .iter()
+                .filter(|a| a.failure_mode_id == self.id && a.covers_latent == latent)
+                .map(|a| a.mechanism.dc(persistence))
+                .reduce(f32::max)
+        };
+
+        if let Some(dc) = best(false) {
+            self.detection_coverage = Some(dc);
+        }
+        if let Some(dc) = best(true) {
+            self.latent_coverage = Some(dc);
+        }
+    }
+
+    /// Failure modes with their coverage resolved from the assigned safety mechanisms
+    pub async fn with_mechanisms(
+        pool: &PgPool,
+        mut failure_modes: Vec<FailureMode>,
+        persistence: FaultPersistence,
+    ) -> Result<Vec<FailureMode>, sqlx::Error> {
+        let ids: Vec<Uuid> = failure_modes.iter().map(|fm| fm.id).collect();
+        let assigned = AssignedMechanism::fetch_for_failure_modes(pool, &ids).await?;
+        for fm in &mut failure_modes {
+            fm.apply_mechanisms(&assigned, persistence);
+        }
+        Ok(failure_modes)
+    }
 }
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug)]
@@ -272,49 +655,693 @@ pub struct Calculation {
     pub created_at: DateTime<Utc>, // updated
 }
 
+// ------------------- Safety Goal Hierarchy -------------------
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct SafetyGoal {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub asil_level: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct System {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct HardwareBlock {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    /// Enclosing block; `None` for a top-level block of the system
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct FunctionalBlock {
+    pub id: Uuid,
+    pub system_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Failure mode of a hardware block mapped to a safety goal it affects
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct GoalMapping {
+    pub hardware_block_id: Uuid,
+    pub failure_mode_id: Uuid,
+    pub safety_goal_id: Uuid,
+    /// Directly violates the goal, rather than only as a multiple-point fault
+    pub violates_goal: bool,
+}
+
+/// Safety goals, systems and blocks of one project
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ProjectHierarchy {
+    pub safety_goals: Vec<SafetyGoal>,
+    pub systems: Vec<System>,
+    pub hardware_blocks: Vec<HardwareBlock>,
+    pub functional_blocks: Vec<FunctionalBlock>,
+    /// (functional block, hardware block) realisation links
+    pub realisations: Vec<(Uuid, Uuid)>,
+    pub goal_mappings: Vec<GoalMapping>,
+}
+
+impl ProjectHierarchy {
+    pub async fn fetch(pool: &PgPool, project_id: Uuid) -> Result<Self, sqlx::Error> {
+        let safety_goals = sqlx::query_as::<_, SafetyGoal>(
+            "SELECT * FROM safety_goals WHERE project_id = $1 ORDER BY name"
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let systems = sqlx::query_as::<_, System>(
+            "SELECT * FROM systems WHERE project_id = $1 ORDER BY name"
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let hardware_blocks = sqlx::query_as::<_, HardwareBlock>(
+            r#"
+            SELECT hb.* FROM hardware_blocks hb
+            JOIN systems s ON s.id = hb.system_id
+            WHERE s.project_id = $1
+            ORDER BY hb.name
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let functional_blocks = sqlx::query_as::<_, FunctionalBlock>(
+            r#"
+            SELECT fb.* FROM functional_blocks fb
+            JOIN systems s ON s.id = fb.system_id
+            WHERE s.project_id = $1
+            ORDER BY fb.name
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let realisations = sqlx::query_as::<_, (Uuid, Uuid)>(
+            r#"
+            SELECT fbh.functional_block_id, fbh.hardware_block_id
+            FROM functional_block_hardware fbh
+            JOIN functional_blocks fb ON fb.id = fbh.functional_block_id
+            JOIN systems s ON s.id = fb.system_id
+            WHERE s.project_id = $1
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        let goal_mappings = sqlx::query_as::<_, GoalMapping>(
+            r#"
+            SELECT m.* FROM block_failure_mode_goals m
+            JOIN safety_goals g ON g.id = m.safety_goal_id
+            WHERE g.project_id = $1
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Self {
+            safety_goals,
+            systems,
+            hardware_blocks,
+            functional_blocks,
+            realisations,
+            goal_mappings,
+        })
+    }
+
+    /// The block and all blocks nested below it
+    pub fn block_subtree(&self, block_id: Uuid) -> Vec<Uuid> {
+        let mut subtree = vec![block_id];
+        let mut i = 0;
+        while i < subtree.len() {
+            let parent = subtree[i];
+            subtree.extend(
+                self.hardware_blocks
+                    .iter()
+                    .filter(|b| b.parent_id == Some(parent) && !subtree.contains(&b.id))
+                    .map(|b| b.id)
+                    .collect::<Vec<_>>(),
+            );
+            i += 1;
+        }
+        subtree
+    }
+
+    /// The block followed by its enclosing blocks, nearest first
+    pub fn block_ancestry(&self, block_id: Uuid) -> Vec<Uuid> {
+        let mut ancestry = vec![block_id];
+        while let Some(parent) = self
+            .hardware_blocks
+            .iter()
+            .find(|b| Some(b.id) == ancestry.last().copied())
+            .and_then(|b| b.parent_id)
+            .filter(|p| !ancestry.contains(p))
+        {
+            ancestry.push(parent);
+        }
+        ancestry
+    }
+}
+
+// ------------------- Failure Mode Distribution Libraries -------------------
+
+/// Tolerance on the sum of the shares of one distribution
+const DISTRIBUTION_SUM_TOLERANCE: f64 = 1e-3;
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct FailureModeLibrary {
+    pub id: Uuid,
+    pub name: String,
+    pub version: i32,
+    pub source: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Share of one failure mode; exactly one of `mpn`, `family`, `component_type` is set
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct DistributionEntry {
+    pub id: Uuid,
+    pub library_id: Uuid,
+    pub mpn: Option<String>,
+    pub family: Option<String>,
+    pub component_type: Option<String>,
+    pub mode: String,
+    pub share: f32,
+}
+
+/// Level at which a distribution was found for a component
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistributionLevel {
+    Mpn,
+    Family,
+    ComponentType,
+}
+
+/// A library version together with its entries
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FailureModeDistributions {
+    pub library: FailureModeLibrary,
+    pub entries: Vec<DistributionEntry>,
+}
+
+impl DistributionEntry {
+    fn selector(&self) -> (DistributionLevel, String) {
+        match (&self.mpn, &self.family, &self.component_type) {
+            (Some(mpn), _, _) => (DistributionLevel::Mpn, mpn.to_lowercase()),
+            (_, Some(family), _) => (DistributionLevel::Family, family.to_lowercase()),
+            (_, _, Some(t)) => (DistributionLevel::ComponentType, t.to_lowercase()),
+            _ => (DistributionLevel::ComponentType, String::new()),
+        }
+    }
+}
+
+impl Component {
+    /// Family used to select a failure-mode distribution: the resistor,
+    /// capacitor or semiconductor type, else the IC technology
+    pub fn family(&self) -> Option<&str> {
+        self.resistor_type
+            .as_deref()
+            .or(self.capacitor_type.as_deref())
+            .or(self.semiconductor_type.as_deref())
+            .or(self.technology.as_deref())
+    }
+}
+
+impl FailureModeDistributions {
+    /// Latest version of the named library
+    pub async fn fetch_latest(pool: &PgPool, name: &str) -> Result<Self, sqlx::Error> {
+        let library = sqlx::query_as::<_, FailureModeLibrary>(
+            "SELECT * FROM failure_mode_libraries WHERE name = $1 ORDER BY version DESC LIMIT 1"
+        )
+        .bind(name)
+        .fetch_one(pool)
+        .await?;
+
+        Self::fetch_entries(pool, library).await
+    }
+
+    /// A specific version of the named library
+    pub async fn fetch_version(pool: &PgPool, name: &str, version: i32) -> Result<Self, sqlx::Error> {
+        let library = sqlx::query_as::<_, FailureModeLibrary>(
+            "SELECT * FROM failure_mode_libraries WHERE name = $1 AND version = $2"
+        )
+        .bind(name)
+        .bind(version)
+        .fetch_one(pool)
+        .await?;
+
+        Self::fetch_entries(pool, library).await
+    }
+
+    async fn fetch_entries(pool: &PgPool, library: FailureModeLibrary) -> Result<Self, sqlx::Error> {
+        let entries = sqlx::query_as::<_, DistributionEntry>(
+            "SELECT * FROM failure_mode_library_entries WHERE library_id = $1 ORDER BY mode"
+        )
+        .bind(library.id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(Self { library, entries })
+    }
+
+    /// Check that the shares of every selector sum to 100 %
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        let mut sums: Vec<((DistributionLevel, String), f64)> = Vec::new();
+        for entry in &self.entries {
+            let selector = entry.selector();
+            match sums.iter_mut().find(|(s, _)| *s == selector) {
+                Some((_, sum)) => *sum += entry.share as f64,
+                None => sums.push((selector, entry.share as f64)),
+            }
+        }
+
+        match sums.into_iter().find(|(_, sum)| (sum - 1.0).abs() > DISTRIBUTION_SUM_TOLERANCE) {
+            Some(((level, key), sum)) => Err(FmedaError::DistributionSum {
+                library: format!("{} v{}", self.library.name, self.library.version),
+                selector: format!("{:?} '{}'", level, key),
+                sum,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Distribution for the component, falling back from MPN to family to component type
+    pub fn select(&self, component: &Component) -> Option<(DistributionLevel, Vec<&DistributionEntry>)> {
+        let candidates = [
+            (DistributionLevel::Mpn, Some(component.manufacturer_part_number.as_str())),
+            (DistributionLevel::Family, component.family()),
+            (DistributionLevel::ComponentType, Some(component.component_type.as_str())),
+        ];
+
+        candidates.into_iter().find_map(|(level, key)| {
+            let key = key?.to_lowercase();
+            let entries: Vec<&DistributionEntry> = self
+                .entries
+                .iter()
+                .filter(|e| e.selector() == (level, key.clone()))
+                .collect();
+            (!entries.is_empty()).then_some((level, entries))
+        })
+    }
+}
+
 // ------------------- FMEDA Prediction -------------------
 
+/// πT of one mission-profile segment
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SegmentPiT {
+    pub temperature: f64,
+    pub tau: f64,
+    pub pi_t: f64,
+}
+
+/// FIT assigned to one failure mode
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FailureModeFit {
+    pub failure_mode_id: Uuid,
+    pub mode: String,
+    /// Share of the component FIT (0–1)
+    pub share: f64,
+    pub fit: f64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FMEDAPredictionResult {
     pub component_id: Uuid,
+    pub standard: String,
+    /// τ-weighted πT, when the standard reports per-segment πT
+    pub weighted_pi_t: Option<f64>,
+    pub segments: Vec<SegmentPiT>,
+    /// Standard FIT × quality factor × quantity
     pub total_fit: f64,
-    pub failure_modes: HashMap<String, f64>,
+    pub failure_modes: Vec<FailureModeFit>,
+    /// Library level the failure-mode shares came from; `None` for λ-weighted failure modes
+    pub distribution: Option<DistributionLevel>,
 }
 
+/// FMEDA prediction for one component: the FIT comes from the selected
+/// reliability standard and is distributed over the failure modes in
+/// proportion to their λ. When no mode has a positive λ the FIT is split
+/// evenly, so that it still counts in the metrics.
 pub fn predict_fmeda(
-    component: &Component,
-    profile: &MissionProfile,
+    standard: &dyn ReliabilityStandard,
+    ctx: &CalcContext,
     failure_modes: &[FailureMode],
-) -> FMEDAPredictionResult {
-    let base_fit = component.base_fit.clone().unwrap_or_else(|| BigDecimal::from(1));
-    let quality = component.quality_factor.clone().unwrap_or_else(|| BigDecimal::from(1));
-
-    let temp_tau = profile.parse_temp_tau_profile();
-    let temp_factor = if !temp_tau.is_empty() {
-        temp_tau.iter().map(|(t, tau)| tau * t).sum::<f64>()
-    } else {
-        1.0
-    };
-
-    let total_fit = base_fit.to_f64().unwrap_or(1.0)
-        * quality.to_f64().unwrap_or(1.0)
-        * temp_factor
-        * component.quantity as f64;
-
-    let mut failure_modes_fit = HashMap::new();
+) -> Result<FMEDAPredictionResult, FmedaError> {
     let lambda_sum: f64 = failure_modes.iter().map(|fm| fm.lambda).sum();
-    for fm in failure_modes {
-        let mode_fit = if lambda_sum > 0.0 {
-            total_fit * (fm.lambda / lambda_sum)
-        } else {
-            0.0
-        };
-        failure_modes_fit.insert(fm.mode.clone(), mode_fit);
+    if lambda_sum <= 0.0 && !failure_modes.is_empty() {
+        report!(
+            "[WARN] {}: failure-mode λ sum to {}, splitting the FIT evenly over {} modes",
+            ctx.component.manufacturer_part_number,
+            lambda_sum,
+            failure_modes.len()
+        );
+    }
+    let shares = failure_modes
+        .iter()
+        .map(|fm| {
+            let share = if lambda_sum > 0.0 {
+                fm.lambda / lambda_sum
+            } else {
+                1.0 / failure_modes.len() as f64
+            };
+            (fm.id, fm.mode.clone(), share)
+        })
+        .collect();
+
+    predict_with_shares(standard, ctx, shares, None)
+}
+
+/// FMEDA prediction for one component with the failure-mode shares taken
+/// from a distribution library (MPN → family → component type).
+/// Each library mode is mapped by name (case-insensitive) to one of the
+/// component's `failure_modes`. A library mode without a failure mode, or a
+/// failure mode without a library share, is an error.
+pub fn predict_fmeda_with_library(
+    standard: &dyn ReliabilityStandard,
+    ctx: &CalcContext,
+    failure_modes: &[FailureMode],
+    library: &FailureModeDistributions,
+) -> Result<FMEDAPredictionResult, FmedaError> {
+    library.validate()?;
+    let (level, entries) = library
+        .select(ctx.component)
+        .ok_or_else(|| FmedaError::NoDistribution(ctx.component.manufacturer_part_number.clone()))?;
+
+    let library_name = || format!("{} v{}", library.library.name, library.library.version);
+    let shares = entries
+        .into_iter()
+        .map(|e| {
+            let failure_mode = failure_modes
+                .iter()
+                .find(|fm| fm.mode.trim().eq_ignore_ascii_case(e.mode.trim()))
+                .ok_or_else(|| FmedaError::UnmatchedLibraryMode {
+                    library: library_name(),
+                    mode: e.mode.clone(),
+                    component: ctx.component.manufacturer_part_number.clone(),
+                })?;
+            Ok((failure_mode.id, failure_mode.mode.clone(), e.share as f64))
+        })
+        .collect::<Result<Vec<_>, FmedaError>>()?;
+
+    if let Some(unmapped) = failure_modes.iter().find(|fm| shares.iter().all(|(id, _, _)| *id != fm.id)) {
+        return Err(FmedaError::UnmappedFailureMode {
+            library: library_name(),
+            mode: unmapped.mode.clone(),
+            component: ctx.component.manufacturer_part_number.clone(),
+        });
+    }
+
+    predict_with_shares(standard, ctx, shares, Some(level))
+}
+
+fn predict_with_shares(
+    standard: &dyn ReliabilityStandard,
+    ctx: &CalcContext,
+    shares: Vec<(Uuid, String, f64)>,
+    distribution: Option<DistributionLevel>,
+) -> Result<FMEDAPredictionResult, FmedaError> {
+    let component = ctx.component;
+    ctx.profile.validate()?;
+
+    if component.base_fit.is_some() {
+        report!(
+            "[WARN] {}: base_fit is ignored; FIT comes from {}",
+            component.manufacturer_part_number,
+            standard.name()
+        );
     }
 
-    FMEDAPredictionResult {
+    let segments: Vec<SegmentPiT> = standard
+        .segment_pi_t(ctx)
+        .map(|pi_ts| {
+            ctx.profile
+                .segments()
+                .iter()
+                .zip(pi_ts)
+                .map(|(s, pi_t)| SegmentPiT { temperature: s.temperature, tau: s.tau, pi_t })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let total_tau: f64 = segments.iter().map(|s| s.tau).sum();
+    let weighted_pi_t = (total_tau > 0.0)
+        .then(|| segments.iter().map(|s| s.pi_t * s.tau).sum::<f64>() / total_tau);
+
+    let quality = component
+        .quality_factor
+        .as_ref()
+        .and_then(|q| q.to_f64())
+        .unwrap_or(1.0);
+
+    let total_fit = standard.calc_fit(ctx)? * quality * component.quantity as f64;
+
+    let failure_modes_fit = shares
+        .into_iter()
+        .map(|(failure_mode_id, mode, share)| FailureModeFit {
+            failure_mode_id,
+            mode,
+            share,
+            fit: total_fit * share,
+        })
+        .collect();
+
+    Ok(FMEDAPredictionResult {
         component_id: component.id,
+        standard: standard.name().to_string(),
+        weighted_pi_t,
+        segments,
         total_fit,
         failure_modes: failure_modes_fit,
+        distribution,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{component, failure_mode, profile, variant, ReferenceFit};
+
+    fn library(entries: &[(&str, f32)]) -> FailureModeDistributions {
+        let library = FailureModeLibrary {
+            id: Uuid::new_v4(),
+            name: "test".to_string(),
+            version: 1,
+            source: None,
+            description: None,
+            created_at: Utc::now(),
+        };
+        let entries = entries
+            .iter()
+            .map(|(mode, share)| DistributionEntry {
+                id: Uuid::new_v4(),
+                library_id: library.id,
+                mpn: None,
+                family: None,
+                component_type: Some("resistor".to_string()),
+                mode: mode.to_string(),
+                share: *share,
+            })
+            .collect();
+        FailureModeDistributions { library, entries }
+    }
+
+    #[test]
+    fn library_modes_map_to_project_failure_modes() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        let modes = [failure_mode("Open", 1.0, None), failure_mode("short", 1.0, None)];
+        let lib = library(&[("open", 0.75), ("short", 0.25)]);
+
+        let result = predict_fmeda_with_library(&ReferenceFit, &ctx, &modes, &lib).unwrap();
+        let open = result.failure_modes.iter().find(|fm| fm.mode == "Open").unwrap();
+        assert_eq!(open.failure_mode_id, modes[0].id);
+        assert!((open.fit - 7.5).abs() < 1e-9);
+        assert_eq!(result.distribution, Some(DistributionLevel::ComponentType));
+        assert!(result.failure_modes.iter().all(|fm| lib.entries.iter().all(|e| e.id != fm.failure_mode_id)));
+    }
+
+    #[test]
+    fn failure_modes_share_the_fit_by_lambda() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        let modes = [failure_mode("open", 3.0, None), failure_mode("short", 1.0, None)];
+
+        let result = predict_fmeda(&ReferenceFit, &ctx, &modes).unwrap();
+        assert_eq!(result.total_fit, 10.0);
+        assert!((result.failure_modes[0].fit - 7.5).abs() < 1e-12);
+        assert!((result.failure_modes[1].share - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn prediction_weights_the_standard_pi_t_by_tau() {
+        // SN 29500 resistor, reference FIT at 40 °C: πT(20 °C) = 0.624015, πT(80 °C) = 2.516379
+        let mut comp = component("resistor");
+        comp.quantity = 2;
+        comp.quality_factor = Some(BigDecimal::from(3));
+        let (var, prof) = (variant(1.0), profile(&[(20.0, 0.25), (80.0, 0.75)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+
+        let result = predict_fmeda(&crate::calc::sn29500::Sn29500, &ctx, &[failure_mode("open", 1.0, None)]).unwrap();
+        assert!((result.segments[0].pi_t - 0.624015).abs() < 1e-6);
+        assert!((result.segments[1].pi_t - 2.516379).abs() < 1e-6);
+        let weighted = result.weighted_pi_t.unwrap();
+        assert!((weighted - 2.043288).abs() < 1e-6);
+        assert!((result.total_fit - 6.0 * weighted).abs() < 1e-9);
+        assert_eq!(result.failure_modes[0].fit, result.total_fit);
+    }
+
+    #[test]
+    fn zero_lambda_splits_the_fit_evenly() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        let modes = [failure_mode("open", 0.0, None), failure_mode("short", 0.0, None)];
+
+        let result = predict_fmeda(&ReferenceFit, &ctx, &modes).unwrap();
+        let sum: f64 = result.failure_modes.iter().map(|fm| fm.fit).sum();
+        assert!((sum - result.total_fit).abs() < 1e-12);
+        assert!(result.failure_modes.iter().all(|fm| fm.share == 0.5));
+    }
+
+    #[test]
+    fn unmatched_library_mode_is_an_error() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        let modes = [failure_mode("open", 1.0, None)];
+        let lib = library(&[("open", 0.75), ("drift", 0.25)]);
+
+        let err = predict_fmeda_with_library(&ReferenceFit, &ctx, &modes, &lib).unwrap_err();
+        assert!(matches!(err, FmedaError::UnmatchedLibraryMode { ref mode, .. } if mode == "drift"));
+    }
+
+    #[test]
+    fn failure_mode_without_library_share_is_an_error() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let ctx = CalcContext::new(&comp, &prof, Some(&var));
+        let modes = [failure_mode("open", 1.0, None), failure_mode("drift", 1.0, None)];
+        let lib = library(&[("open", 1.0)]);
+
+        let err = predict_fmeda_with_library(&ReferenceFit, &ctx, &modes, &lib).unwrap_err();
+        assert!(matches!(err, FmedaError::UnmappedFailureMode { ref mode, .. } if mode == "drift"));
+    }
+
+    fn parse(value: serde_json::Value) -> Result<TempTauProfile, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    #[test]
+    fn legacy_profile_layouts_upgrade_to_segments() {
+        let expected = parse(serde_json::json!({
+            "version": 2,
+            "segments": [{ "temperature": 25.0, "tau": 0.4 }, { "temperature": 60.0, "tau": 0.6 }]
+        }))
+        .unwrap();
+        assert_eq!(expected.version, MISSION_PROFILE_VERSION);
+        assert_eq!(expected.temp_tau_pairs(), vec![(25.0, 0.4), (60.0, 0.6)]);
+
+        let pairs = parse(serde_json::json!([{ "temp": 25.0, "tau": 0.4 }, { "temp": 60.0, "tau": 0.6 }]));
+        let unversioned = parse(serde_json::json!({
+            "segments": [{ "temperature": 25.0, "tau": 0.4 }, { "temperature": 60.0, "tau": 0.6 }]
+        }));
+        let parallel = parse(serde_json::json!({ "temperature": [25.0, 60.0], "tau": [0.4, 0.6] }));
+        for upgraded in [pairs, unversioned, parallel] {
+            assert_eq!(upgraded.unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn malformed_profile_layouts_are_rejected() {
+        assert!(parse(serde_json::json!({ "temperature": [25.0], "tau": [0.4, 0.6] })).is_err());
+        assert!(parse(serde_json::json!({ "version": 3, "segments": [] })).is_err());
+    }
+
+    #[test]
+    fn profile_validation_reports_the_offending_segment() {
+        assert!(profile(&[(25.0, 0.4), (60.0, 0.6)]).validate().is_ok());
+        assert!(profile(&[(25.0, 3000.0), (60.0, 5760.0)]).validate().is_ok());
+
+        let error = |segments: &[(f64, f64)]| profile(segments).validate().unwrap_err();
+        assert!(matches!(error(&[]), FmedaError::EmptyMissionProfile));
+        assert!(matches!(error(&[(25.0, 1.2), (60.0, -0.2)]), FmedaError::NegativeTau { index: 1, .. }));
+        assert!(matches!(error(&[(25.0, 0.5), (60.0, 0.4)]), FmedaError::TauNotNormalised { .. }));
+        assert!(matches!(error(&[(-300.0, 1.0)]), FmedaError::TemperatureBelowAbsoluteZero { index: 0, .. }));
+        assert!(matches!(error(&[(400.0, 1.0)]), FmedaError::TemperatureOutOfRange { index: 0, .. }));
+        assert!(matches!(error(&[(25.0, 0.5), (25.0, 0.5)]), FmedaError::DuplicateSegment { index: 1, first: 0 }));
+
+        let mut not_finite = profile(&[(25.0, 1.0)]);
+        not_finite.temp_tau_profile.segments[0].temperature = f64::NAN;
+        assert!(matches!(not_finite.validate(), Err(FmedaError::InvalidSegment { index: 0, .. })));
+
+        let mut cycling = profile(&[(25.0, 1.0)]);
+        cycling.temp_tau_profile.segments[0].delta_t = Some(20.0);
+        assert!(matches!(cycling.validate(), Err(FmedaError::InvalidSegment { index: 0, .. })));
+    }
+
+    fn mechanism(failure_mode_id: Uuid, covers_latent: bool, dc_permanent: f32, dc_transient: Option<f32>) -> AssignedMechanism {
+        AssignedMechanism {
+            failure_mode_id,
+            covers_latent,
+            mechanism: SafetyMechanism {
+                id: Uuid::new_v4(),
+                name: "test".to_string(),
+                description: None,
+                dc_permanent,
+                dc_transient,
+                annex_d_reference: None,
+                test_interval_hours: None,
+                created_at: Utc::now(),
+            },
+        }
+    }
+
+    #[test]
+    fn mechanisms_set_the_highest_claimed_coverage() {
+        let mut fm = failure_mode("open", 1.0, Some(0.5));
+        fm.latent_coverage = Some(0.3);
+        let assigned = [
+            mechanism(fm.id, false, 0.9, Some(0.6)),
+            mechanism(fm.id, false, 0.99, None),
+            mechanism(Uuid::new_v4(), false, 1.0, None),
+        ];
+
+        let mut permanent = fm.clone();
+        permanent.apply_mechanisms(&assigned, FaultPersistence::Permanent);
+        assert_eq!(permanent.detection_coverage, Some(0.99));
+        // No latent mechanism: the stored latent coverage stays
+        assert_eq!(permanent.latent_coverage, Some(0.3));
+
+        // Transient falls back to the permanent DC where none is claimed
+        let mut transient = fm.clone();
+        transient.apply_mechanisms(&assigned, FaultPersistence::Transient);
+        assert_eq!(transient.detection_coverage, Some(0.99));
+
+        let mut latent = fm.clone();
+        latent.apply_mechanisms(&[mechanism(fm.id, true, 0.9, Some(0.6))], FaultPersistence::Transient);
+        assert_eq!(latent.latent_coverage, Some(0.6));
+        assert_eq!(latent.detection_coverage, Some(0.5));
     }
 }
@@ -0,0 +1,377 @@
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::errors::FmedaError;
+use crate::models::ComponentVariant;
+
+/// Hours per FIT unit (FIT = failures per 1e9 h)
+const HOURS_PER_FIT_UNIT: f64 = 1.0 / FIT_TO_PER_HOUR;
+
+/// Default shape of the gamma prior built from the handbook FIT
+pub const DEFAULT_PRIOR_SHAPE: f64 = 0.5;
+
+/// Field return data of a component variant
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct FieldData {
+    pub id: Uuid,
+    pub variant_id: Uuid,
+    /// Origin of the data, e.g. warranty database, 8D report, customer
+    pub source: String,
+    pub failures: i32,
+    /// Cumulative fleet operating hours
+    pub operating_hours: f64,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Gamma prior on the failure rate: mean = `fit`, shape α0
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct GammaPrior {
+    pub fit: f64,
+    pub shape: f64,
+}
+
+impl GammaPrior {
+    /// Prior centred on the handbook reference FIT of the variant
+    pub fn from_variant(variant: &ComponentVariant, shape: Option<f64>) -> Self {
+        Self { fit: variant.ref_fit, shape: shape.unwrap_or(DEFAULT_PRIOR_SHAPE) }
+    }
+}
+
+/// FIT estimates from field data
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FieldFitEstimate {
+    pub field_data_id: Option<Uuid>,
+    pub source: String,
+    pub failures: u32,
+    pub operating_hours: f64,
+    /// Point estimate r / T (FIT)
+    pub point_fit: f64,
+    /// Chi-square upper limit at 60 % confidence (FIT)
+    pub upper_60: f64,
+    /// Chi-square upper limit at 90 % confidence (FIT)
+    pub upper_90: f64,
+    pub prior: GammaPrior,
+    /// Gamma-posterior mean (FIT)
+    pub posterior_fit: f64,
+    /// Gamma-posterior 90 % upper bound (FIT)
+    pub posterior_upper_90: f64,
+    pub posterior_shape: f64,
+    /// Posterior rate parameter β (hours / 1e9)
+    pub posterior_rate: f64,
+}
+
+impl FieldData {
+    pub async fn fetch_for_variant(pool: &PgPool, variant_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, FieldData>(
+            "SELECT * FROM field_data WHERE variant_id = $1 ORDER BY created_at"
+        )
+        .bind(variant_id)
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Chi-square upper confidence limit of a time-terminated observation:
+/// λU = χ²(CL; 2r + 2) / 2T, in FIT
+pub fn chi_square_upper_limit(failures: u32, operating_hours: f64, confidence: f64) -> Result<f64, FmedaError> {
+    validate(operating_hours)?;
+    if !(0.0..1.0).contains(&confidence) {
+        return Err(FmedaError::InvalidFieldData(format!("confidence {} outside [0, 1)", confidence)));
+    }
+    let dof = 2.0 * failures as f64 + 2.0;
+    Ok(chi_square_quantile(confidence, dof) / (2.0 * operating_hours) * HOURS_PER_FIT_UNIT)
+}
+
+/// Chi-square limits and gamma-posterior FIT from the observed failures
+/// and operating hours. The prior Gamma(α0, β0) has mean `prior.fit`, i.e.
+/// β0 = α0 / λ0; the posterior is Gamma(α0 + r, β0 + T).
+pub fn estimate(
+    source: &str,
+    failures: u32,
+    operating_hours: f64,
+    prior: GammaPrior,
+) -> Result<FieldFitEstimate, FmedaError> {
+    validate(operating_hours)?;
+    if prior.fit <= 0.0 || prior.shape <= 0.0 {
+        return Err(FmedaError::InvalidFieldData("gamma prior needs a positive FIT and shape".to_string()));
+    }
+
+    // Work in units of 1e9 h so that rates are in FIT
+    let t = operating_hours / HOURS_PER_FIT_UNIT;
+    let prior_rate = prior.shape / prior.fit;
+    let posterior_shape = prior.shape + failures as f64;
+    let posterior_rate = prior_rate + t;
+
+    let estimate = FieldFitEstimate {
+        field_data_id: None,
+        source: source.to_string(),
+        failures,
+        operating_hours,
+        point_fit: failures as f64 / t,
+        upper_60: chi_square_upper_limit(failures, operating_hours, 0.60)?,
+        upper_90: chi_square_upper_limit(failures, operating_hours, 0.90)?,
+        prior,
+        posterior_fit: posterior_shape / posterior_rate,
+        posterior_upper_90: gamma_quantile(0.90, posterior_shape) / posterior_rate,
+        posterior_shape,
+        posterior_rate,
+    };
+
+    report!(
+        "[FieldData] {} | r = {} | T = {:.3e} h | χ²60% = {:.6} | χ²90% = {:.6} | posterior = {:.6} FIT",
+        source, failures, operating_hours, estimate.upper_60, estimate.upper_90, estimate.posterior_fit
+    );
+
+    Ok(estimate)
+}
+
+/// Estimate from a stored field-data record with the variant's handbook FIT as prior
+pub fn estimate_for_variant(
+    data: &FieldData,
+    variant: &ComponentVariant,
+    prior_shape: Option<f64>,
+) -> Result<FieldFitEstimate, FmedaError> {
+    if data.failures < 0 {
+        return Err(FmedaError::InvalidFieldData(format!("{} failures", data.failures)));
+    }
+    let mut estimate = estimate(
+        &data.source,
+        data.failures as u32,
+        data.operating_hours,
+        GammaPrior::from_variant(variant, prior_shape),
+    )?;
+    estimate.field_data_id = Some(data.id);
+    Ok(estimate)
+}
+
+impl FieldFitEstimate {
+    /// Variant with `fit` recorded as its field FIT, keeping the handbook `ref_fit`
+    /// and noting the data source
+    pub fn apply(&self, variant: &ComponentVariant, fit: f64) -> ComponentVariant {
+        ComponentVariant {
+            notes: Some(format!(
+                "Field FIT from '{}' ({} failures in {:.3e} h), handbook {:.6}",
+                self.source, self.failures, self.operating_hours, variant.ref_fit
+            )),
+            field_fit: Some(fit),
+            field_data_id: self.field_data_id,
+            ..variant.clone()
+        }
+    }
+
+    /// Record `fit` as the field-justified FIT of the variant, keeping the handbook reference FIT.
+    /// The field data the estimate came from must belong to the variant.
+    pub async fn store(&self, pool: &PgPool, variant_id: Uuid, fit: f64) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE component_variants SET field_fit = $2, field_data_id = $3
+            WHERE id = $1
+              AND ($3 IS NULL OR EXISTS (SELECT 1 FROM field_data WHERE id = $3 AND variant_id = $1))
+            "#
+        )
+        .bind(variant_id)
+        .bind(fit)
+        .bind(self.field_data_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
+
+fn validate(operating_hours: f64) -> Result<(), FmedaError> {
+    if operating_hours > 0.0 && operating_hours.is_finite() {
+        Ok(())
+    } else {
+        Err(FmedaError::InvalidFieldData(format!("operating hours {}", operating_hours)))
+    }
+}
+
+/// Quantile of the chi-square distribution with `dof` degrees of freedom
+fn chi_square_quantile(p: f64, dof: f64) -> f64 {
+    2.0 * gamma_quantile(p, dof / 2.0)
+}
+
+/// Quantile of the Gamma(shape, 1) distribution by bisection on P(a, x)
+fn gamma_quantile(p: f64, shape: f64) -> f64 {
+    if p <= 0.0 {
+        return 0.0;
+    }
+    let mut high = shape.max(1.0);
+    while regularized_gamma_p(shape, high) < p {
+        high *= 2.0;
+    }
+    let mut low = 0.0;
+    for _ in 0..200 {
+        let mid = 0.5 * (low + high);
+        if regularized_gamma_p(shape, mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+        if high - low <= 1e-12 * high {
+            break;
+        }
+    }
+    0.5 * (low + high)
+}
+
+/// Regularized lower incomplete gamma function P(a, x)
+fn regularized_gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let log_prefix = a * x.ln() - x - ln_gamma(a);
+
+    if x < a + 1.0 {
+        // Series expansion
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..1000 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (sum.ln() + log_prefix).exp()
+    } else {
+        // Continued fraction for Q(a, x) (modified Lentz)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        1.0 - (log_prefix.exp() * h)
+    }
+}
+
+/// ln Γ(x) by the Lanczos approximation (g = 7, n = 9)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection formula
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::variant;
+
+    fn close(actual: f64, expected: f64, tolerance: f64) -> bool {
+        (actual - expected).abs() <= tolerance * expected.abs().max(1e-300)
+    }
+
+    #[test]
+    fn chi_square_limits_without_failures() {
+        // r = 0: λU = −ln(1 − CL) / T, i.e. 2.3026/T at 90 % and 0.9163/T at 60 %
+        let t = 1e9;
+        assert!(close(chi_square_upper_limit(0, t, 0.90).unwrap(), std::f64::consts::LN_10, 1e-6));
+        assert!(close(chi_square_upper_limit(0, t, 0.60).unwrap(), 0.916291, 1e-6));
+    }
+
+    #[test]
+    fn chi_square_limits_with_failures() {
+        // χ²(0.90; 4) = 7.779440, χ²(0.60; 22) = 23.03066
+        assert!(close(chi_square_upper_limit(1, 1e9, 0.90).unwrap(), 7.779440 / 2.0, 1e-6));
+        assert!(close(chi_square_upper_limit(10, 1e9, 0.60).unwrap(), 23.03066 / 2.0, 1e-6));
+        assert!(chi_square_upper_limit(0, 0.0, 0.9).is_err());
+        assert!(chi_square_upper_limit(0, 1e9, 1.0).is_err());
+    }
+
+    #[test]
+    fn gamma_quantiles() {
+        // Gamma(1) is exponential: x = −ln(1 − p)
+        assert!(close(gamma_quantile(0.5, 1.0), 2f64.ln(), 1e-10));
+        // Gamma(1/2) = χ²(1)/2: χ²(0.90; 1) = 2.705543
+        assert!(close(gamma_quantile(0.90, 0.5), 2.705543 / 2.0, 1e-6));
+        // χ²(0.95; 10) = 18.30704
+        assert!(close(chi_square_quantile(0.95, 10.0), 18.30704, 1e-6));
+        assert_eq!(gamma_quantile(0.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn ln_gamma_matches_factorials() {
+        assert!(close(ln_gamma(5.0), 24f64.ln(), 1e-12));
+        assert!(close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12));
+        assert!(close(regularized_gamma_p(1.0, 1.0), 1.0 - (-1f64).exp(), 1e-12));
+    }
+
+    #[test]
+    fn gamma_posterior_updates_the_prior() {
+        // Prior mean 10 FIT, α0 = 0.5 → β0 = 0.05 (1e9 h); 2 failures in 1e8 h
+        let prior = GammaPrior::from_variant(&variant(10.0), None);
+        let estimate = estimate("fleet", 2, 1e8, prior).unwrap();
+        assert!(close(estimate.point_fit, 20.0, 1e-12));
+        assert!(close(estimate.posterior_shape, 2.5, 1e-12));
+        assert!(close(estimate.posterior_rate, 0.15, 1e-12));
+        assert!(close(estimate.posterior_fit, 2.5 / 0.15, 1e-12));
+        assert!(estimate.posterior_upper_90 > estimate.posterior_fit);
+    }
+
+    #[test]
+    fn applied_field_fit_is_the_reference_fit() {
+        let handbook = variant(10.0);
+        let estimate = estimate("fleet", 0, 1e9, GammaPrior::from_variant(&handbook, None)).unwrap();
+        let applied = estimate.apply(&handbook, 2.5);
+        assert_eq!(applied.reference_fit(), 2.5);
+        assert_eq!(applied.ref_fit, 10.0);
+        // The prior stays on the handbook FIT, so the field data is not counted twice
+        assert_eq!(GammaPrior::from_variant(&applied, None).fit, 10.0);
+        let recorded = ComponentVariant { field_fit: Some(2.5), ..handbook.clone() };
+        assert_eq!(recorded.reference_fit(), 2.5);
+        assert_eq!(handbook.reference_fit(), 10.0);
+    }
+}
@@ -0,0 +1,392 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::FmedaError;
+use crate::safety::pfd::{ChannelRates, ProofTestParams};
+
+/// Largest Λ·Δt handled in one uniformization step, keeps e^(−Λ·Δt) representable
+const MAX_UNIFORMIZATION_STEP: f64 = 50.0;
+
+/// Truncation error of the uniformization series
+const UNIFORMIZATION_EPSILON: f64 = 1e-14;
+
+/// Condition of the system in a state
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StateKind {
+    Up,
+    /// Working with reduced redundancy
+    Degraded,
+    /// Failed with the failure detected: unavailable, but the system is brought to a safe state
+    DetectedDown,
+    /// Safety function lost by an undetected dangerous failure
+    Down,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarkovState {
+    pub name: String,
+    pub kind: StateKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Transition {
+    pub from: usize,
+    pub to: usize,
+    /// Transition rate (1/h)
+    pub rate: f64,
+}
+
+/// Continuous-time Markov chain of a repairable architecture
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarkovModel {
+    pub states: Vec<MarkovState>,
+    pub transitions: Vec<Transition>,
+    /// State at t = 0
+    pub initial: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StateProbabilities {
+    pub t_h: f64,
+    pub probabilities: Vec<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MarkovResult {
+    pub mission_time_h: f64,
+    /// State probabilities over the mission time
+    pub transient: Vec<StateProbabilities>,
+    /// Time-averaged probability of being unavailable, the PFDavg of a low-demand function
+    pub pfd_avg: f64,
+    /// Average frequency of entering a dangerous (`Down`) state (1/h)
+    pub pfh: f64,
+    /// Probability of being available at the end of the mission
+    pub availability: f64,
+    /// Long-run availability; `None` when the chain has no unique steady state
+    pub steady_state_availability: Option<f64>,
+}
+
+impl MarkovModel {
+    /// Repair rates of detected and undetected failures:
+    /// μDD = 1/MTTR, μDU = 1/(T1/2 + MRT)
+    fn repair_rates(params: &ProofTestParams) -> (f64, f64) {
+        let mu_dd = if params.mttr_h > 0.0 { 1.0 / params.mttr_h } else { 0.0 };
+        let mu_du = 1.0 / (params.proof_test_interval_h / 2.0 + params.mttr_h);
+        (mu_dd, mu_du)
+    }
+
+    fn state(name: &str, kind: StateKind) -> MarkovState {
+        MarkovState { name: name.to_string(), kind }
+    }
+
+    /// Single channel: OK → DD / DU failure, repaired after detection or proof test
+    pub fn one_oo_one(channel: ChannelRates, params: &ProofTestParams) -> Self {
+        let (mu_dd, mu_du) = Self::repair_rates(params);
+        Self {
+            states: vec![
+                Self::state("OK", StateKind::Up),
+                Self::state("DD failure", StateKind::DetectedDown),
+                Self::state("DU failure", StateKind::Down),
+            ],
+            transitions: vec![
+                Transition { from: 0, to: 1, rate: channel.lambda_dd },
+                Transition { from: 0, to: 2, rate: channel.lambda_du },
+                Transition { from: 1, to: 0, rate: mu_dd },
+                Transition { from: 2, to: 0, rate: mu_du },
+            ],
+            initial: 0,
+        }
+    }
+
+    /// N identical channels tolerating one fault (1oo2 for n = 2, 2oo3 for n = 3):
+    /// one independent failure degrades the system, a second one or a
+    /// common-cause failure takes it down; the system failure is undetected
+    /// when the last failure is DU
+    pub fn single_fault_tolerant(n: u32, channel: ChannelRates, params: &ProofTestParams) -> Result<Self, FmedaError> {
+        if n < 2 {
+            return Err(FmedaError::InvalidArchitecture(format!("{}-channel single-fault-tolerant", n)));
+        }
+        let (mu_dd, mu_du) = Self::repair_rates(params);
+        let n = n as f64;
+        let ChannelRates { lambda_du, lambda_dd, .. } = channel;
+
+        Ok(Self {
+            states: vec![
+                Self::state("all OK", StateKind::Up),
+                Self::state("one channel DD", StateKind::Degraded),
+                Self::state("one channel DU", StateKind::Degraded),
+                Self::state("system failed, detected", StateKind::DetectedDown),
+                Self::state("system failed, undetected", StateKind::Down),
+            ],
+            transitions: vec![
+                Transition { from: 0, to: 1, rate: n * (1.0 - params.beta_d) * lambda_dd },
+                Transition { from: 0, to: 2, rate: n * (1.0 - params.beta) * lambda_du },
+                Transition { from: 0, to: 3, rate: params.beta_d * lambda_dd },
+                Transition { from: 0, to: 4, rate: params.beta * lambda_du },
+                Transition { from: 1, to: 3, rate: (n - 1.0) * lambda_dd },
+                Transition { from: 1, to: 4, rate: (n - 1.0) * lambda_du },
+                Transition { from: 1, to: 0, rate: mu_dd },
+                Transition { from: 2, to: 3, rate: (n - 1.0) * lambda_dd },
+                Transition { from: 2, to: 4, rate: (n - 1.0) * lambda_du },
+                Transition { from: 2, to: 0, rate: mu_du },
+                Transition { from: 3, to: 0, rate: mu_dd },
+                Transition { from: 4, to: 0, rate: mu_du },
+            ],
+            initial: 0,
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        let n = self.states.len();
+        if n == 0 || self.initial >= n {
+            return Err(FmedaError::InvalidMarkovModel("initial state out of range".to_string()));
+        }
+        if let Some(t) = self
+            .transitions
+            .iter()
+            .find(|t| t.from >= n || t.to >= n || t.from == t.to || !(t.rate >= 0.0 && t.rate.is_finite()))
+        {
+            return Err(FmedaError::InvalidMarkovModel(format!(
+                "transition {} → {} with rate {}",
+                t.from, t.to, t.rate
+            )));
+        }
+        Ok(())
+    }
+
+    /// Generator matrix Q (rows sum to zero)
+    fn generator(&self) -> Vec<Vec<f64>> {
+        let n = self.states.len();
+        let mut q = vec![vec![0.0; n]; n];
+        for t in &self.transitions {
+            q[t.from][t.to] += t.rate;
+            q[t.from][t.from] -= t.rate;
+        }
+        q
+    }
+
+    /// Safety function unavailable (detected or undetected failure)
+    fn is_unavailable(&self, state: usize) -> bool {
+        matches!(self.states[state].kind, StateKind::DetectedDown | StateKind::Down)
+    }
+
+    fn is_dangerous(&self, state: usize) -> bool {
+        self.states[state].kind == StateKind::Down
+    }
+
+    /// p(t + Δt) = p(t)·e^(QΔt) by uniformization
+    fn propagate(q: &[Vec<f64>], p: &[f64], dt: f64) -> Vec<f64> {
+        let n = p.len();
+        let lambda = q.iter().enumerate().map(|(i, row)| -row[i]).fold(0.0, f64::max);
+        if lambda == 0.0 || dt == 0.0 {
+            return p.to_vec();
+        }
+
+        let substeps = (lambda * dt / MAX_UNIFORMIZATION_STEP).ceil().max(1.0) as usize;
+        let h = dt / substeps as f64;
+        let lt = lambda * h;
+
+        let mut p = p.to_vec();
+        for _ in 0..substeps {
+            let mut term = p.clone();
+            let mut weight = (-lt).exp();
+            let mut result: Vec<f64> = term.iter().map(|x| x * weight).collect();
+            let mut accumulated = weight;
+            let mut k = 0;
+            while 1.0 - accumulated > UNIFORMIZATION_EPSILON && k < 10_000 {
+                k += 1;
+                // term = term·P with P = I + Q/Λ
+                let mut next = term.clone();
+                for (i, row) in q.iter().enumerate() {
+                    for (j, qij) in row.iter().enumerate() {
+                        next[j] += term[i] * qij / lambda;
+                    }
+                }
+                term = next;
+                weight *= lt / k as f64;
+                accumulated += weight;
+                for j in 0..n {
+                    result[j] += term[j] * weight;
+                }
+            }
+            p = result;
+        }
+        p
+    }
+
+    /// Steady-state distribution π with πQ = 0, Σπ = 1 (Gaussian elimination)
+    fn steady_state(q: &[Vec<f64>]) -> Option<Vec<f64>> {
+        let n = q.len();
+        // Transposed system Qᵀπ = 0 with the last equation replaced by Σπ = 1
+        let mut a: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                let mut row: Vec<f64> = (0..n).map(|j| q[j][i]).collect();
+                row.push(0.0);
+                row
+            })
+            .collect();
+        a[n - 1] = vec![1.0; n + 1];
+
+        for col in 0..n {
+            let pivot = (col..n).max_by(|&x, &y| a[x][col].abs().total_cmp(&a[y][col].abs()))?;
+            if a[pivot][col].abs() < 1e-300 {
+                return None;
+            }
+            a.swap(col, pivot);
+            let pivot_row = a[col].clone();
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != col {
+                    let factor = values[col] / pivot_row[col];
+                    for (x, p) in values[col..].iter_mut().zip(&pivot_row[col..]) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+        }
+        Some((0..n).map(|i| a[i][n] / a[i][i]).collect())
+    }
+}
+
+/// Transient state probabilities, PFDavg, PFH and availability over the mission time
+pub fn solve(model: &MarkovModel, mission_time_h: f64, steps: usize) -> Result<MarkovResult, FmedaError> {
+    model.validate()?;
+    if mission_time_h <= 0.0 {
+        return Err(FmedaError::Calculation("mission time must be positive".to_string()));
+    }
+
+    let n = model.states.len();
+    let q = model.generator();
+    let steps = steps.max(1);
+    let dt = mission_time_h / steps as f64;
+
+    let mut p = vec![0.0; n];
+    p[model.initial] = 1.0;
+
+    let down_probability = |p: &[f64]| (0..n).filter(|&i| model.is_unavailable(i)).map(|i| p[i]).sum::<f64>();
+    // Σ p_i·q_ij into dangerous states j from states i that are not
+    let failure_frequency = |p: &[f64]| {
+        model
+            .transitions
+            .iter()
+            .filter(|t| !model.is_dangerous(t.from) && model.is_dangerous(t.to))
+            .map(|t| p[t.from] * t.rate)
+            .sum::<f64>()
+    };
+
+    let mut transient = vec![StateProbabilities { t_h: 0.0, probabilities: p.clone() }];
+    let (mut down_integral, mut frequency_integral) = (0.0, 0.0);
+    for step in 1..=steps {
+        let next = MarkovModel::propagate(&q, &p, dt);
+        down_integral += (down_probability(&p) + down_probability(&next)) / 2.0 * dt;
+        frequency_integral += (failure_frequency(&p) + failure_frequency(&next)) / 2.0 * dt;
+        p = next;
+        transient.push(StateProbabilities { t_h: step as f64 * dt, probabilities: p.clone() });
+    }
+
+    let pfd_avg = down_integral / mission_time_h;
+    let pfh = frequency_integral / mission_time_h;
+    let availability = 1.0 - down_probability(&p);
+    let steady_state_availability = MarkovModel::steady_state(&q).map(|pi| 1.0 - down_probability(&pi));
+
+    report!(
+        "[Markov] {} states | PFDavg = {:.6e} | PFH = {:.6e} /h | A({:.0} h) = {:.9}",
+        n, pfd_avg, pfh, mission_time_h, availability
+    );
+
+    Ok(MarkovResult {
+        mission_time_h,
+        transient,
+        pfd_avg,
+        pfh,
+        availability,
+        steady_state_availability,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::pfd::{pfd_pfh, Architecture};
+
+    fn params(beta: f64) -> ProofTestParams {
+        ProofTestParams {
+            proof_test_interval_h: 8760.0,
+            mttr_h: 8.0,
+            beta,
+            beta_d: beta,
+            comparison_coverage: 0.98,
+        }
+    }
+
+    fn channel(dc: f64) -> ChannelRates {
+        let lambda_d = 1e-6;
+        ChannelRates { lambda_du: lambda_d * (1.0 - dc), lambda_dd: lambda_d * dc, lambda_sd: 0.0 }
+    }
+
+    /// Steady-state frequency of entering a dangerous state (1/h)
+    fn steady_state_pfh(model: &MarkovModel) -> f64 {
+        let pi = MarkovModel::steady_state(&model.generator()).unwrap();
+        model
+            .transitions
+            .iter()
+            .filter(|t| !model.is_dangerous(t.from) && model.is_dangerous(t.to))
+            .map(|t| pi[t.from] * t.rate)
+            .sum()
+    }
+
+    #[test]
+    fn non_repairable_unit_decays_exponentially() {
+        let lambda = 1e-4;
+        let model = MarkovModel {
+            states: vec![MarkovModel::state("up", StateKind::Up), MarkovModel::state("down", StateKind::Down)],
+            transitions: vec![Transition { from: 0, to: 1, rate: lambda }],
+            initial: 0,
+        };
+        let t = 10_000.0;
+        let result = solve(&model, t, 100).unwrap();
+        assert!((result.availability - (-lambda * t).exp()).abs() < 1e-9);
+        // PFDavg = 1 − (1 − e^(−λT))/(λT)
+        let expected = 1.0 - (1.0 - (-lambda * t).exp()) / (lambda * t);
+        assert!((result.pfd_avg - expected).abs() < 1e-4);
+        assert_eq!(result.steady_state_availability, Some(0.0));
+    }
+
+    #[test]
+    fn one_oo_one_steady_state_unavailability() {
+        // Two independent repair loops: U = λDD/μDD + λDU/μDU relative to the up state
+        let (c, p) = (channel(0.6), params(0.0));
+        let model = MarkovModel::one_oo_one(c, &p);
+        let down = c.lambda_dd * p.mttr_h + c.lambda_du * (p.proof_test_interval_h / 2.0 + p.mttr_h);
+        let expected = down / (1.0 + down);
+        let availability = solve(&model, 8760.0, 10).unwrap().steady_state_availability.unwrap();
+        assert!(((1.0 - availability) - expected).abs() / expected < 1e-9);
+    }
+
+    #[test]
+    fn one_oo_two_pfh_matches_closed_form() {
+        // Without CCF the CTMC reproduces PFH = 2·λD·λDU·tCE of IEC 61508-6 B.3.3.2.2,
+        // up to the second-order terms (2·λD·tCE ≈ 1 %) the simplified equation drops
+        for dc in [0.0, 0.6, 0.9] {
+            let (c, p) = (channel(dc), params(0.0));
+            let model = MarkovModel::single_fault_tolerant(2, c, &p).unwrap();
+            let closed_form = pfd_pfh(Architecture::OneOoTwo, c, p).unwrap().pfh;
+            let markov = steady_state_pfh(&model);
+            assert!((markov - closed_form).abs() / closed_form < 0.02, "DC {}: {} vs {}", dc, markov, closed_form);
+        }
+    }
+
+    #[test]
+    fn one_oo_two_common_cause_dominates() {
+        // With β = 10 % the CCF term βλDU dominates both results
+        let (c, p) = (channel(0.9), params(0.1));
+        let model = MarkovModel::single_fault_tolerant(2, c, &p).unwrap();
+        let closed_form = pfd_pfh(Architecture::OneOoTwo, c, p).unwrap().pfh;
+        let markov = steady_state_pfh(&model);
+        assert!((markov - closed_form).abs() / closed_form < 0.01);
+    }
+
+    #[test]
+    fn rejects_single_channel_redundancy() {
+        assert!(MarkovModel::single_fault_tolerant(1, channel(0.0), &params(0.0)).is_err());
+    }
+}
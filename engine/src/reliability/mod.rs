@@ -0,0 +1,4 @@
+pub mod field;
+pub mod markov;
+pub mod rbd;
+pub mod weibull;
@@ -0,0 +1,396 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::errors::FmedaError;
+use crate::models::FMEDAPredictionResult;
+
+/// Relative tolerance under which standby unit rates count as identical
+const IDENTICAL_RATE_TOLERANCE: f64 = 1e-9;
+
+/// Block of a reliability block diagram
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    /// Constant-rate item; the FIT comes from the component's FMEDA prediction,
+    /// `fit` is only used for items without a component
+    Unit {
+        name: Option<String>,
+        component_id: Option<Uuid>,
+        fit: Option<f64>,
+    },
+    /// All blocks needed
+    Series { blocks: Vec<Block> },
+    /// One of the blocks needed (active redundancy)
+    Parallel { blocks: Vec<Block> },
+    /// At least `k` of the blocks needed (active redundancy)
+    KOutOfN { k: usize, blocks: Vec<Block> },
+    /// Cold standby: units take over one after the other; each switchover
+    /// succeeds with `switch_reliability` (default 1)
+    Standby {
+        blocks: Vec<Block>,
+        switch_reliability: Option<f64>,
+    },
+}
+
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+pub struct ReliabilityBlockDiagram {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub diagram: Json<Block>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReliabilityBlockDiagram {
+    pub async fn fetch(pool: &PgPool, id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as::<_, ReliabilityBlockDiagram>("SELECT * FROM reliability_block_diagrams WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+    }
+}
+
+/// Point of the reliability curve
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct CurvePoint {
+    pub t_h: f64,
+    pub reliability: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RbdResult {
+    pub mission_time_h: f64,
+    /// R(mission time)
+    pub reliability: f64,
+    /// MTTF (h); `None` when zero-FIT units keep the diagram working for ever
+    pub mttf_h: Option<f64>,
+    /// Constant rate with the same R(mission time): −ln R / t (FIT);
+    /// `None` when R(mission time) = 0
+    pub equivalent_fit: Option<f64>,
+    pub curve: Vec<CurvePoint>,
+}
+
+impl Block {
+    /// Resolve the FIT of every unit from the component FMEDA predictions
+    pub fn bind_fmeda(&mut self, predictions: &[FMEDAPredictionResult]) {
+        match self {
+            Block::Unit { component_id: Some(id), fit, .. } => {
+                if let Some(p) = predictions.iter().find(|p| p.component_id == *id) {
+                    *fit = Some(p.total_fit);
+                }
+            }
+            Block::Unit { .. } => {}
+            Block::Series { blocks }
+            | Block::Parallel { blocks }
+            | Block::KOutOfN { blocks, .. }
+            | Block::Standby { blocks, .. } => blocks.iter_mut().for_each(|b| b.bind_fmeda(predictions)),
+        }
+    }
+
+    /// Check that every unit has a FIT and every group its required blocks
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        let invalid = |msg: &str| Err(FmedaError::InvalidBlockDiagram(msg.to_string()));
+        match self {
+            Block::Unit { fit: Some(fit), .. } if *fit >= 0.0 => Ok(()),
+            Block::Unit { name, .. } => Err(FmedaError::InvalidBlockDiagram(format!(
+                "unit '{}' has no FIT",
+                name.as_deref().unwrap_or("?")
+            ))),
+            Block::Series { blocks } | Block::Parallel { blocks } | Block::Standby { blocks, .. }
+                if blocks.is_empty() =>
+            {
+                invalid("group without blocks")
+            }
+            Block::KOutOfN { k, blocks } if *k == 0 || *k > blocks.len() => invalid("k-out-of-n with k outside 1..=n"),
+            Block::Standby { blocks, switch_reliability } => {
+                if switch_reliability.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+                    return invalid("standby switch reliability outside [0, 1]");
+                }
+                if !blocks.iter().all(|b| matches!(b, Block::Unit { .. })) {
+                    return invalid("standby blocks must be units");
+                }
+                blocks.iter().try_for_each(Block::validate)?;
+                standby_rates(blocks).map(|_| ())
+            }
+            Block::Series { blocks } | Block::Parallel { blocks } | Block::KOutOfN { blocks, .. } => {
+                blocks.iter().try_for_each(Block::validate)
+            }
+        }
+    }
+
+    /// R(t) of a validated block
+    pub fn reliability(&self, t_h: f64) -> f64 {
+        match self {
+            Block::Unit { fit, .. } => (-fit.unwrap_or(0.0) * FIT_TO_PER_HOUR * t_h).exp(),
+            Block::Series { blocks } => blocks.iter().map(|b| b.reliability(t_h)).product(),
+            Block::Parallel { blocks } => 1.0 - blocks.iter().map(|b| 1.0 - b.reliability(t_h)).product::<f64>(),
+            Block::KOutOfN { k, blocks } => {
+                let r: Vec<f64> = blocks.iter().map(|b| b.reliability(t_h)).collect();
+                at_least_k(*k, &r)
+            }
+            Block::Standby { blocks, switch_reliability } => {
+                // Σ p^i·P(unit i + 1 is running at t): every switchover needs its own success
+                let rates = standby_rates(blocks).unwrap_or_default();
+                let p = switch_reliability.unwrap_or(1.0);
+                let mut reliability = 0.0;
+                let mut r_previous = 0.0;
+                let mut weight = 1.0;
+                for units in 1..=rates.len() {
+                    let r_chain = standby_reliability(&rates[..units], t_h);
+                    reliability += weight * (r_chain - r_previous);
+                    r_previous = r_chain;
+                    weight *= p;
+                }
+                reliability
+            }
+        }
+    }
+
+    /// Whether R(t) stays above zero for ever, through units with a zero FIT
+    fn survives_indefinitely(&self) -> bool {
+        match self {
+            Block::Unit { fit, .. } => fit.unwrap_or(0.0) == 0.0,
+            Block::Series { blocks } => blocks.iter().all(Block::survives_indefinitely),
+            Block::Parallel { blocks } => blocks.iter().any(Block::survives_indefinitely),
+            Block::KOutOfN { k, blocks } => blocks.iter().filter(|b| b.survives_indefinitely()).count() >= *k,
+            Block::Standby { blocks, switch_reliability } => blocks
+                .iter()
+                .enumerate()
+                .any(|(i, b)| b.survives_indefinitely() && (i == 0 || switch_reliability.unwrap_or(1.0) > 0.0)),
+        }
+    }
+
+    /// Slowest unit rate in the diagram (1/h), sets the time scale of the MTTF integration
+    fn min_rate(&self) -> Option<f64> {
+        match self {
+            Block::Unit { fit, .. } => fit.map(|f| f * FIT_TO_PER_HOUR).filter(|r| *r > 0.0),
+            Block::Series { blocks }
+            | Block::Parallel { blocks }
+            | Block::KOutOfN { blocks, .. }
+            | Block::Standby { blocks, .. } => blocks.iter().filter_map(Block::min_rate).reduce(f64::min),
+        }
+    }
+
+    fn unit_count(&self) -> usize {
+        match self {
+            Block::Unit { .. } => 1,
+            Block::Series { blocks }
+            | Block::Parallel { blocks }
+            | Block::KOutOfN { blocks, .. }
+            | Block::Standby { blocks, .. } => blocks.iter().map(Block::unit_count).sum(),
+        }
+    }
+}
+
+/// R(t) curve, MTTF and equivalent failure rate of the diagram over the mission time
+pub fn evaluate(diagram: &Block, mission_time_h: f64, points: usize) -> Result<RbdResult, FmedaError> {
+    diagram.validate()?;
+    if mission_time_h <= 0.0 {
+        return Err(FmedaError::Calculation("mission time must be positive".to_string()));
+    }
+
+    let points = points.max(2);
+    let curve = (0..points)
+        .map(|i| {
+            let t_h = mission_time_h * i as f64 / (points - 1) as f64;
+            CurvePoint { t_h, reliability: diagram.reliability(t_h) }
+        })
+        .collect();
+
+    let reliability = diagram.reliability(mission_time_h);
+    let mttf_h = mttf(diagram);
+    let equivalent_fit = (reliability > 0.0).then(|| -reliability.ln() / mission_time_h / FIT_TO_PER_HOUR);
+
+    report!(
+        "[RBD] R({:.0} h) = {:.9} | MTTF = {:.3e} h | λeq = {:.6} FIT",
+        mission_time_h,
+        reliability,
+        mttf_h.unwrap_or(f64::INFINITY),
+        equivalent_fit.unwrap_or(f64::INFINITY)
+    );
+
+    Ok(RbdResult { mission_time_h, reliability, mttf_h, equivalent_fit, curve })
+}
+
+/// MTTF = ∫R(t)dt by Simpson's rule, up to a horizon where R is negligible.
+/// `None` (infinite) when zero-FIT units keep the diagram working for ever.
+fn mttf(diagram: &Block) -> Option<f64> {
+    if diagram.survives_indefinitely() {
+        report!("[WARN] Zero-FIT units keep the diagram working indefinitely → MTTF = ∞");
+        return None;
+    }
+    let min_rate = diagram.min_rate()?;
+
+    // Every unit failing in turn bounds the life by roughly n / λmin
+    let mut horizon = 10.0 * diagram.unit_count() as f64 / min_rate;
+    while diagram.reliability(horizon) > 1e-12 {
+        horizon *= 2.0;
+    }
+
+    const INTERVALS: usize = 20_000;
+    let h = horizon / INTERVALS as f64;
+    let sum: f64 = (0..=INTERVALS)
+        .map(|i| {
+            let weight = match i {
+                0 | INTERVALS => 1.0,
+                i if i % 2 == 1 => 4.0,
+                _ => 2.0,
+            };
+            weight * diagram.reliability(i as f64 * h)
+        })
+        .sum();
+    Some(sum * h / 3.0)
+}
+
+/// Probability that at least `k` of the independent blocks work (Poisson-binomial)
+fn at_least_k(k: usize, reliabilities: &[f64]) -> f64 {
+    // working[j] = P(exactly j blocks work)
+    let mut working = vec![0.0; reliabilities.len() + 1];
+    working[0] = 1.0;
+    for (n, r) in reliabilities.iter().enumerate() {
+        for j in (0..=n + 1).rev() {
+            let up = if j > 0 { working[j - 1] * r } else { 0.0 };
+            working[j] = working[j] * (1.0 - r) + up;
+        }
+    }
+    working[k..].iter().sum()
+}
+
+/// Unit rates (1/h) of a standby group; the rates must be all identical or all distinct
+fn standby_rates(blocks: &[Block]) -> Result<Vec<f64>, FmedaError> {
+    let rates: Vec<f64> = blocks
+        .iter()
+        .map(|b| match b {
+            Block::Unit { fit, .. } => fit.unwrap_or(0.0) * FIT_TO_PER_HOUR,
+            _ => 0.0,
+        })
+        .collect();
+
+    let all_identical = rates.windows(2).all(|w| same_rate(w[0], w[1]));
+    let all_distinct = rates
+        .iter()
+        .enumerate()
+        .all(|(i, a)| rates[i + 1..].iter().all(|b| !same_rate(*a, *b)));
+
+    if all_identical || all_distinct {
+        Ok(rates)
+    } else {
+        Err(FmedaError::InvalidBlockDiagram(
+            "standby unit rates must be all identical or all distinct".to_string(),
+        ))
+    }
+}
+
+fn same_rate(a: f64, b: f64) -> bool {
+    (a - b).abs() <= IDENTICAL_RATE_TOLERANCE * a.abs().max(b.abs())
+}
+
+/// R(t) of a cold-standby chain with perfect switching:
+/// identical rates → Erlang, e^(−λt)·Σ(λt)^j/j!;
+/// distinct rates → hypoexponential, Σ e^(−λi·t)·Π_(j≠i) λj/(λj − λi)
+fn standby_reliability(rates: &[f64], t_h: f64) -> f64 {
+    let n = rates.len();
+    if rates.iter().all(|r| *r == 0.0) {
+        return 1.0;
+    }
+    if rates.windows(2).all(|w| same_rate(w[0], w[1])) {
+        let lt = rates[0] * t_h;
+        let mut term = 1.0;
+        let mut sum = 1.0;
+        for j in 1..n {
+            term *= lt / j as f64;
+            sum += term;
+        }
+        return (-lt).exp() * sum;
+    }
+
+    rates
+        .iter()
+        .enumerate()
+        .map(|(i, li)| {
+            let coefficient: f64 = rates
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, lj)| lj / (lj - li))
+                .product();
+            coefficient * (-li * t_h).exp()
+        })
+        .sum::<f64>()
+        .clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIT: f64 = 1000.0;
+    const LAMBDA: f64 = FIT * FIT_TO_PER_HOUR;
+
+    fn unit(fit: f64) -> Block {
+        Block::Unit { name: None, component_id: None, fit: Some(fit) }
+    }
+
+    fn standby(n: usize, switch_reliability: Option<f64>) -> Block {
+        Block::Standby { blocks: (0..n).map(|_| unit(FIT)).collect(), switch_reliability }
+    }
+
+    #[test]
+    fn two_unit_cold_standby_is_erlang() {
+        let block = standby(2, None);
+        for t in [0.0, 1e5, 1e6, 5e6] {
+            let lt = LAMBDA * t;
+            let expected = lt * (-lt).exp() + (-lt).exp();
+            assert!((block.reliability(t) - expected).abs() < 1e-12);
+        }
+        let mttf = mttf(&block).unwrap();
+        assert!((mttf - 2.0 / LAMBDA).abs() / (2.0 / LAMBDA) < 1e-6);
+    }
+
+    #[test]
+    fn every_switchover_is_weighted() {
+        // R = e^(−λt)·[1 + p·λt + p²·(λt)²/2]
+        let p = 0.9;
+        let block = standby(3, Some(p));
+        let t = 1e6;
+        let lt = LAMBDA * t;
+        let expected = (-lt).exp() * (1.0 + p * lt + p * p * lt * lt / 2.0);
+        assert!((block.reliability(t) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn distinct_standby_rates_are_hypoexponential() {
+        let block = Block::Standby { blocks: vec![unit(1000.0), unit(2000.0)], switch_reliability: None };
+        let (l1, l2, t): (f64, f64, f64) = (1e-6, 2e-6, 5e5);
+        let expected = (l2 * (-l1 * t).exp() - l1 * (-l2 * t).exp()) / (l2 - l1);
+        assert!((block.reliability(t) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn zero_fit_redundancy_has_infinite_mttf() {
+        let block = Block::Standby { blocks: vec![unit(FIT), unit(0.0)], switch_reliability: Some(0.5) };
+        assert_eq!(mttf(&block), None);
+        let block = Block::Parallel { blocks: vec![unit(FIT), unit(0.0)] };
+        assert_eq!(evaluate(&block, 1000.0, 2).unwrap().mttf_h, None);
+        // Serialised as null with the documented meaning, never as a bare infinity
+        let json = serde_json::to_value(evaluate(&block, 1000.0, 2).unwrap()).unwrap();
+        assert!(json["mttf_h"].is_null());
+        // A zero-FIT unit in series does not stop the others from failing
+        let block = Block::Series { blocks: vec![unit(FIT), unit(0.0)] };
+        assert!((mttf(&block).unwrap() - 1.0 / LAMBDA).abs() * LAMBDA < 1e-6);
+    }
+
+    #[test]
+    fn k_out_of_n_and_parallel() {
+        let t = 1e6;
+        let r = (-LAMBDA * t).exp();
+        let two_of_three = Block::KOutOfN { k: 2, blocks: vec![unit(FIT), unit(FIT), unit(FIT)] };
+        assert!((two_of_three.reliability(t) - (3.0 * r * r - 2.0 * r * r * r)).abs() < 1e-12);
+        let parallel = Block::Parallel { blocks: vec![unit(FIT), unit(FIT)] };
+        assert!((mttf(&parallel).unwrap() - 1.5 / LAMBDA).abs() * LAMBDA < 1e-6);
+    }
+}
@@ -0,0 +1,342 @@
+use serde::{Deserialize, Serialize};
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::errors::FmedaError;
+use crate::models::ComponentVariant;
+
+/// Default share of the constant rate the wear-out hazard may reach
+/// before the constant-rate assumption is considered invalid
+pub const DEFAULT_VALIDITY_TOLERANCE: f64 = 0.1;
+
+/// Weibull wear-out: R(t) = exp[−(t/η)^β]
+///
+/// Only [`lifetime`] uses this model; `predict_fmeda` and the RBD keep the
+/// constant-rate FIT of the standards and ignore the variant's Weibull columns.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct WeibullModel {
+    /// Shape β; β > 1 for wear-out, β < 1 for infant mortality
+    pub beta: f64,
+    /// Characteristic life η (h)
+    pub eta_h: f64,
+}
+
+impl WeibullModel {
+    pub fn new(beta: f64, eta_h: f64) -> Result<Self, FmedaError> {
+        if beta > 0.0 && eta_h > 0.0 && beta.is_finite() && eta_h.is_finite() {
+            Ok(Self { beta, eta_h })
+        } else {
+            Err(FmedaError::InvalidWeibull { beta, eta: eta_h })
+        }
+    }
+
+    /// Wear-out model of the variant, if it has one
+    pub fn from_variant(variant: &ComponentVariant) -> Result<Option<Self>, FmedaError> {
+        match (variant.weibull_beta, variant.weibull_eta_hours) {
+            (Some(beta), Some(eta_h)) => Self::new(beta, eta_h).map(Some),
+            (None, None) => Ok(None),
+            (beta, eta) => Err(FmedaError::InvalidWeibull {
+                beta: beta.unwrap_or(f64::NAN),
+                eta: eta.unwrap_or(f64::NAN),
+            }),
+        }
+    }
+
+    pub fn reliability(&self, t_h: f64) -> f64 {
+        (-(t_h.max(0.0) / self.eta_h).powf(self.beta)).exp()
+    }
+
+    /// Hazard h(t) = (β/η)·(t/η)^(β−1) (1/h)
+    pub fn hazard(&self, t_h: f64) -> f64 {
+        if t_h <= 0.0 {
+            return if self.beta < 1.0 {
+                f64::INFINITY
+            } else if self.beta == 1.0 {
+                1.0 / self.eta_h
+            } else {
+                0.0
+            };
+        }
+        self.beta / self.eta_h * (t_h / self.eta_h).powf(self.beta - 1.0)
+    }
+
+    /// Time at which the hazard crosses `rate` (1/h): rising through it for
+    /// β > 1, falling through it for β < 1; `None` when the hazard is constant (β = 1)
+    pub fn time_to_hazard(&self, rate: f64) -> Option<f64> {
+        (self.beta != 1.0 && rate > 0.0)
+            .then(|| self.eta_h * (rate * self.eta_h / self.beta).powf(1.0 / (self.beta - 1.0)))
+    }
+}
+
+/// Point of the time-dependent reliability curves
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct LifetimePoint {
+    pub t_h: f64,
+    /// R(t) of constant rate and wear-out combined
+    pub reliability: f64,
+    /// F(t) = 1 − R(t)
+    pub cumulative_failure: f64,
+    /// Total hazard λ + h(t) (FIT)
+    pub hazard_fit: f64,
+    /// R(t) under the constant-rate assumption alone
+    pub constant_rate_reliability: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LifetimeResult {
+    pub mission_lifetime_h: f64,
+    pub constant_fit: f64,
+    pub weibull: Option<WeibullModel>,
+    pub reliability: f64,
+    pub cumulative_failure: f64,
+    /// Constant rate with the same R over the lifetime: −ln R(T) / T (FIT);
+    /// `None` when R(T) = 0
+    pub average_fit: Option<f64>,
+    /// Time at which the wear-out hazard reaches `tolerance`·λ; `None` without wear-out,
+    /// 0 when the Weibull hazard already exceeds it at t = 0
+    pub constant_rate_valid_until_h: Option<f64>,
+    /// Infant mortality (β < 1): time until which the decreasing hazard stays
+    /// above `tolerance`·λ, capped at the mission lifetime; `None` when β ≥ 1
+    pub early_failures_until_h: Option<f64>,
+    /// Constant-rate assumption holds over the whole mission lifetime
+    pub constant_rate_valid: bool,
+    /// Curve points; with β < 1 the curve starts after t = 0, where the hazard is unbounded
+    pub curve: Vec<LifetimePoint>,
+}
+
+/// R(t), F(t) and hazard over the mission lifetime for a constant rate
+/// (the standard FIT) competing with optional Weibull wear-out:
+///
+/// R(t) = exp(−λt)·exp[−(t/η)^β],  h(t) = λ + (β/η)·(t/η)^(β−1)
+///
+/// The constant-rate assumption stops being valid where the wear-out
+/// hazard reaches `tolerance`·λ. With β < 1 the hazard is infinite at t = 0
+/// and falls, so the assumption fails from the start of the mission; with
+/// β = 1 it fails throughout if 1/η exceeds `tolerance`·λ.
+pub fn lifetime(
+    constant_fit: f64,
+    weibull: Option<WeibullModel>,
+    mission_lifetime_h: f64,
+    points: usize,
+    tolerance: Option<f64>,
+) -> Result<LifetimeResult, FmedaError> {
+    if !(mission_lifetime_h.is_finite() && mission_lifetime_h > 0.0) {
+        return Err(FmedaError::Calculation(format!(
+            "mission lifetime must be positive and finite, got {}",
+            mission_lifetime_h
+        )));
+    }
+    if !(constant_fit.is_finite() && constant_fit >= 0.0) {
+        return Err(FmedaError::Calculation(format!("invalid FIT {}", constant_fit)));
+    }
+    let tolerance = tolerance.unwrap_or(DEFAULT_VALIDITY_TOLERANCE);
+    if !(tolerance.is_finite() && tolerance > 0.0) {
+        return Err(FmedaError::Calculation(format!(
+            "validity tolerance must be positive and finite, got {}",
+            tolerance
+        )));
+    }
+
+    let lambda = constant_fit * FIT_TO_PER_HOUR;
+
+    let point = |t_h: f64| {
+        let constant_rate_reliability = (-lambda * t_h).exp();
+        let reliability = constant_rate_reliability * weibull.map_or(1.0, |w| w.reliability(t_h));
+        let hazard = lambda + weibull.map_or(0.0, |w| w.hazard(t_h));
+        LifetimePoint {
+            t_h,
+            reliability,
+            cumulative_failure: 1.0 - reliability,
+            hazard_fit: hazard / FIT_TO_PER_HOUR,
+            constant_rate_reliability,
+        }
+    };
+
+    let points = points.max(2);
+    let first = usize::from(weibull.is_some_and(|w| w.beta < 1.0));
+    let curve: Vec<LifetimePoint> = (first..points)
+        .map(|i| point(mission_lifetime_h * i as f64 / (points - 1) as f64))
+        .collect();
+
+    let end = point(mission_lifetime_h);
+    let average_fit =
+        (end.reliability > 0.0).then(|| -end.reliability.ln() / mission_lifetime_h / FIT_TO_PER_HOUR);
+
+    let limit = tolerance * lambda;
+    let constant_rate_valid_until_h = weibull.and_then(|w| {
+        if w.beta > 1.0 {
+            // Without a constant rate any wear-out dominates
+            Some(w.time_to_hazard(limit).unwrap_or(0.0))
+        } else if w.beta < 1.0 || 1.0 / w.eta_h > limit {
+            Some(0.0)
+        } else {
+            None
+        }
+    });
+    let constant_rate_valid = constant_rate_valid_until_h.is_none_or(|t| t >= mission_lifetime_h);
+
+    let early_failures_until_h = weibull
+        .filter(|w| w.beta < 1.0)
+        .map(|w| w.time_to_hazard(limit).map_or(mission_lifetime_h, |t| t.min(mission_lifetime_h)));
+    if let (Some(w), Some(t)) = (weibull, early_failures_until_h) {
+        report!(
+            "[WARN] Weibull β = {} < 1 (infant mortality): hazard exceeds {:.0} % of λ until {:.0} h",
+            w.beta,
+            tolerance * 100.0,
+            t
+        );
+    }
+    let wear_out_exceeds = !constant_rate_valid && early_failures_until_h.is_none();
+    if let Some(t) = constant_rate_valid_until_h.filter(|_| wear_out_exceeds) {
+        report!(
+            "[WARN] Wear-out hazard exceeds {:.0} % of λ after {:.0} h (< mission lifetime {:.0} h)",
+            tolerance * 100.0,
+            t,
+            mission_lifetime_h
+        );
+    }
+    report!(
+        "[Weibull] λ = {:.6} FIT | R({:.0} h) = {:.9} | F = {:.6e} | λavg = {:.6} FIT",
+        constant_fit,
+        mission_lifetime_h,
+        end.reliability,
+        end.cumulative_failure,
+        average_fit.unwrap_or(f64::INFINITY)
+    );
+
+    Ok(LifetimeResult {
+        mission_lifetime_h,
+        constant_fit,
+        weibull,
+        reliability: end.reliability,
+        cumulative_failure: end.cumulative_failure,
+        average_fit,
+        constant_rate_valid_until_h,
+        early_failures_until_h,
+        constant_rate_valid,
+        curve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1e-300)
+    }
+
+    #[test]
+    fn reliability_and_hazard_follow_closed_form() {
+        let w = WeibullModel::new(2.0, 1.0e5).unwrap();
+        assert!(close(w.reliability(1.0e5), (-1.0f64).exp()));
+        assert!(close(w.reliability(5.0e4), (-0.25f64).exp()));
+        assert_eq!(w.reliability(0.0), 1.0);
+        // h(t) = β/η·(t/η)^(β−1) = 2t/η²
+        assert!(close(w.hazard(5.0e4), 2.0 * 5.0e4 / 1.0e10));
+        assert_eq!(w.hazard(0.0), 0.0);
+
+        let exp = WeibullModel::new(1.0, 1.0e6).unwrap();
+        assert!(close(exp.hazard(123.0), 1.0e-6));
+        assert!(WeibullModel::new(0.5, 1.0e6).unwrap().hazard(0.0).is_infinite());
+    }
+
+    #[test]
+    fn combined_curve_multiplies_reliabilities() {
+        let w = WeibullModel::new(3.0, 2.0e5).unwrap();
+        let r = lifetime(100.0, Some(w), 1.0e5, 11, None).unwrap();
+        let expected = (-100.0 * FIT_TO_PER_HOUR * 1.0e5).exp() * (-(0.5f64).powi(3)).exp();
+        assert!(close(r.reliability, expected));
+        assert!(close(r.cumulative_failure, 1.0 - expected));
+        assert!(close(r.average_fit.unwrap(), -expected.ln() / 1.0e5 / FIT_TO_PER_HOUR));
+        assert_eq!(r.curve.len(), 11);
+        assert_eq!(r.curve[10].t_h, 1.0e5);
+
+        let constant = lifetime(100.0, None, 1.0e5, 2, None).unwrap();
+        assert!(close(constant.average_fit.unwrap(), 100.0));
+        assert!(constant.constant_rate_valid);
+        assert_eq!(constant.constant_rate_valid_until_h, None);
+    }
+
+    #[test]
+    fn validity_point_is_where_wear_out_reaches_tolerance() {
+        // β = 2, η = 1e5 h: h(t) = 2t/η² = 0.1·λ at t = 0.05·λ·η²
+        let w = WeibullModel::new(2.0, 1.0e5).unwrap();
+        let lambda = 1000.0 * FIT_TO_PER_HOUR;
+        let t = 0.05 * lambda * 1.0e10;
+        let r = lifetime(1000.0, Some(w), 1.0e5, 2, None).unwrap();
+        let until = r.constant_rate_valid_until_h.unwrap();
+        assert!(close(until, t));
+        assert!(close(w.hazard(until), DEFAULT_VALIDITY_TOLERANCE * lambda));
+        assert!(r.constant_rate_valid == (t >= 1.0e5));
+
+        let short = lifetime(1000.0, Some(w), until * 0.5, 2, None).unwrap();
+        assert!(short.constant_rate_valid);
+        let long = lifetime(1000.0, Some(w), until * 2.0, 2, None).unwrap();
+        assert!(!long.constant_rate_valid);
+
+        let no_constant = lifetime(0.0, Some(w), 1.0, 2, None).unwrap();
+        assert_eq!(no_constant.constant_rate_valid_until_h, Some(0.0));
+        assert!(!no_constant.constant_rate_valid);
+    }
+
+    #[test]
+    fn infant_mortality_is_not_constant_rate_valid() {
+        // h(t) falls to 0.1·λ = 1e-8 /h at t = η·(0.1·λ·η/β)^(1/(β−1)) = 2.5e8 h
+        let w = WeibullModel::new(0.5, 1.0e7).unwrap();
+        let r = lifetime(100.0, Some(w), 1.0e9, 2, None).unwrap();
+        assert!(!r.constant_rate_valid);
+        assert_eq!(r.constant_rate_valid_until_h, Some(0.0));
+        let until = r.early_failures_until_h.unwrap();
+        assert!(close(until, 2.5e8));
+        assert!(close(w.hazard(until), DEFAULT_VALIDITY_TOLERANCE * 100.0 * FIT_TO_PER_HOUR));
+        // Capped at a shorter mission lifetime
+        let short = lifetime(100.0, Some(w), 1.0e5, 2, None).unwrap();
+        assert_eq!(short.early_failures_until_h, Some(1.0e5));
+
+        let w = WeibullModel::new(2.0, 1.0e7).unwrap();
+        let wear_out = lifetime(100.0, Some(w), 1.0e5, 2, None).unwrap();
+        assert_eq!(wear_out.early_failures_until_h, None);
+    }
+
+    #[test]
+    fn infant_mortality_curve_has_no_unbounded_values() {
+        // The t = 0 point, where h(t) is infinite, is left out of the curve
+        let w = WeibullModel::new(0.5, 1.0e7).unwrap();
+        let r = lifetime(0.0, Some(w), 1.0e5, 5, None).unwrap();
+        assert_eq!(r.curve.len(), 4);
+        assert_eq!(r.curve[0].t_h, 2.5e4);
+        assert!(r.curve.iter().all(|p| p.hazard_fit.is_finite()));
+        // Without a constant rate the early failures dominate the whole mission
+        assert_eq!(r.early_failures_until_h, Some(1.0e5));
+
+        // R(T) = 0 leaves the average rate undefined
+        let worn = lifetime(0.0, Some(WeibullModel::new(2.0, 1.0).unwrap()), 1.0e5, 2, None).unwrap();
+        assert_eq!(worn.reliability, 0.0);
+        assert_eq!(worn.average_fit, None);
+    }
+
+    #[test]
+    fn beta_one_compares_its_constant_hazard() {
+        // 1/η = 1e-8 /h = 10 FIT: within 10 % of 1000 FIT, not of 10 FIT
+        let w = WeibullModel::new(1.0, 1.0e8).unwrap();
+        assert!(lifetime(1000.0, Some(w), 1.0e5, 2, None).unwrap().constant_rate_valid);
+        let r = lifetime(10.0, Some(w), 1.0e5, 2, None).unwrap();
+        assert!(!r.constant_rate_valid);
+        assert_eq!(r.constant_rate_valid_until_h, Some(0.0));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        for t in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(lifetime(10.0, None, t, 2, None).is_err());
+        }
+        for tol in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            assert!(lifetime(10.0, None, 1.0e5, 2, Some(tol)).is_err());
+        }
+        for fit in [-1.0, f64::NAN, f64::INFINITY] {
+            assert!(lifetime(fit, None, 1.0e5, 2, None).is_err());
+        }
+        assert!(WeibullModel::new(0.0, 1.0).is_err());
+        assert!(WeibullModel::new(2.0, f64::NAN).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! Report lines of the calculations ([SN29500], [PMHF], [WARN], ...) printed to stdout
+
+use std::cell::Cell;
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether report lines are printed on this thread
+pub(crate) fn enabled() -> bool {
+    !QUIET.with(Cell::get)
+}
+
+/// Restores the previous setting when dropped, also on panic
+struct QuietGuard(bool);
+
+impl Drop for QuietGuard {
+    fn drop(&mut self) {
+        QUIET.with(|q| q.set(self.0));
+    }
+}
+
+/// Run `f` without report lines, for the many evaluations of a sampling loop
+pub(crate) fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = QuietGuard(QUIET.with(|q| q.replace(true)));
+    f()
+}
+
+/// `println!` that is silenced inside [`quietly`]
+macro_rules! report {
+    ($($arg:tt)*) => {
+        if $crate::report::enabled() {
+            println!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quietly_restores_the_previous_setting() {
+        assert!(enabled());
+        quietly(|| {
+            assert!(!enabled());
+            quietly(|| assert!(!enabled()));
+            assert!(!enabled());
+        });
+        assert!(enabled());
+    }
+}
@@ -1,27 +1,43 @@
-use axum::{extract::Json, routing::post, Router};
+use axum::{extract::{Json, State}, routing::post, Router};
 use serde::Deserialize;
-use crate::models::Component;
-use crate::calc::calculate_fit;
+use crate::calc::{calculate_fit, StandardRegistry};
+use crate::errors::FmedaError;
+use crate::models::{Component, ComponentVariant};
 
 #[derive(Deserialize)]
 struct CalcRequest {
     standard: String,
     components: Vec<Component>,
+    #[serde(default)]
+    variants: Vec<ComponentVariant>,
     mission_profile: crate::models::MissionProfile,
 }
 
-pub fn routes() -> Router {
-    Router::new().route("/calculate", post(run_calculation))
+/// API routes; the registry is shared as application state
+pub fn routes(registry: StandardRegistry) -> Router {
+    Router::new()
+        .route("/calculate", post(run_calculation))
+        .with_state(registry)
 }
 
-async fn run_calculation(Json(req): Json<CalcRequest>) -> Json<Vec<(String, f64)>> {
+async fn run_calculation(
+    State(registry): State<StandardRegistry>,
+    Json(req): Json<CalcRequest>,
+) -> Result<Json<Vec<(String, f64)>>, FmedaError> {
+    // Reject bad mission profiles instead of falling back to reference FITs
+    req.mission_profile.validate()?;
+
     let results = req
         .components
         .iter()
         .map(|c| {
-            (c.manufacturer_part_number.clone(), calculate_fit(&req.standard, c, &req.mission_profile))
+            let variant = c
+                .variant_id
+                .and_then(|vid| req.variants.iter().find(|v| v.id == vid));
+            let fit = calculate_fit(&registry, &req.standard, c, &req.mission_profile, variant)?;
+            Ok((c.manufacturer_part_number.clone(), fit))
         })
-        .collect();
+        .collect::<Result<Vec<_>, FmedaError>>()?;
 
-    Json(results)
+    Ok(Json(results))
 }
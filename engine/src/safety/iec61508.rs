@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::errors::FmedaError;
+use crate::models::{FMEDAPredictionResult, FailureMode};
+
+/// IEC 61508-2 §7.4.4.1 element type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementType {
+    /// Well-known failure modes and behaviour under fault conditions
+    A,
+    /// Complex elements (e.g. microcontrollers) with incompletely defined behaviour
+    B,
+}
+
+impl FromStr for ElementType {
+    type Err = FmedaError;
+
+    /// Accepts "A", "Type B", "type-b", ...
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = s.trim().to_uppercase().replace(['-', '_', ' '], "");
+        match normalised.trim_start_matches("TYPE") {
+            "A" => Ok(ElementType::A),
+            "B" => Ok(ElementType::B),
+            _ => Err(FmedaError::InvalidElementType(s.to_string())),
+        }
+    }
+}
+
+/// IEC 61508 Safety Integrity Level
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sil {
+    Sil1 = 1,
+    Sil2 = 2,
+    Sil3 = 3,
+    Sil4 = 4,
+}
+
+impl fmt::Display for Sil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SIL {}", *self as u8)
+    }
+}
+
+/// λ of one failure mode split into safe, dangerous detected and dangerous undetected
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LambdaSplit {
+    pub failure_mode_id: Uuid,
+    pub mode: String,
+    pub lambda_s: f64,
+    pub lambda_dd: f64,
+    pub lambda_du: f64,
+}
+
+/// Safe Failure Fraction and Route 1H architectural SIL capability of an element
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SilCapability {
+    pub element_type: ElementType,
+    pub hft: u32,
+    pub lambda_s: f64,
+    pub lambda_dd: f64,
+    pub lambda_du: f64,
+    /// (λS + λDD) / (λS + λDD + λDU); `None` without any λ
+    pub sff: Option<f64>,
+    /// Highest SIL claimable; `None` when the architecture is not allowed
+    pub max_sil: Option<Sil>,
+}
+
+/// Split the FIT of a failure mode:
+/// λS = λ·safe_fraction, λDD = λD·DC, λDU = λD·(1 − DC)
+pub fn split_lambda(failure_mode: &FailureMode, fit: f64) -> LambdaSplit {
+    let fraction = |v: Option<f32>| (v.unwrap_or(0.0) as f64).clamp(0.0, 1.0);
+
+    let lambda_s = fit * fraction(failure_mode.safe_fraction);
+    let lambda_d = fit - lambda_s;
+    let dc = fraction(failure_mode.detection_coverage);
+
+    LambdaSplit {
+        failure_mode_id: failure_mode.id,
+        mode: failure_mode.mode.clone(),
+        lambda_s,
+        lambda_dd: lambda_d * dc,
+        lambda_du: lambda_d * (1.0 - dc),
+    }
+}
+
+/// λ splits of the failure-mode FITs of an FMEDA prediction
+pub fn split_prediction(prediction: &FMEDAPredictionResult, failure_modes: &[FailureMode]) -> Vec<LambdaSplit> {
+    prediction
+        .failure_modes
+        .iter()
+        .filter_map(|fm_fit| {
+            let fm = failure_modes.iter().find(|fm| fm.id == fm_fit.failure_mode_id)?;
+            Some(split_lambda(fm, fm_fit.fit))
+        })
+        .collect()
+}
+
+/// IEC 61508-2 Tables 2 and 3 (Route 1H): maximum SIL for the SFF and HFT.
+/// An HFT above 2 is treated as 2.
+pub fn route_1h(element_type: ElementType, sff: f64, hft: u32) -> Option<Sil> {
+    use Sil::*;
+
+    // Rows: SFF < 60 %, 60–90 %, 90–99 %, ≥ 99 %; columns: HFT 0, 1, 2
+    const TYPE_A: [[Option<Sil>; 3]; 4] = [
+        [Some(Sil1), Some(Sil2), Some(Sil3)],
+        [Some(Sil2), Some(Sil3), Some(Sil4)],
+        [Some(Sil3), Some(Sil4), Some(Sil4)],
+        [Some(Sil3), Some(Sil4), Some(Sil4)],
+    ];
+    const TYPE_B: [[Option<Sil>; 3]; 4] = [
+        [None, Some(Sil1), Some(Sil2)],
+        [Some(Sil1), Some(Sil2), Some(Sil3)],
+        [Some(Sil2), Some(Sil3), Some(Sil4)],
+        [Some(Sil3), Some(Sil4), Some(Sil4)],
+    ];
+
+    let row = match sff {
+        s if s < 0.60 => 0,
+        s if s < 0.90 => 1,
+        s if s < 0.99 => 2,
+        _ => 3,
+    };
+    let column = hft.min(2) as usize;
+
+    match element_type {
+        ElementType::A => TYPE_A[row][column],
+        ElementType::B => TYPE_B[row][column],
+    }
+}
+
+/// SFF of the element and its Route 1H SIL capability at the given HFT
+pub fn sil_capability(element_type: ElementType, hft: u32, splits: &[LambdaSplit]) -> SilCapability {
+    let lambda_s: f64 = splits.iter().map(|s| s.lambda_s).sum();
+    let lambda_dd: f64 = splits.iter().map(|s| s.lambda_dd).sum();
+    let lambda_du: f64 = splits.iter().map(|s| s.lambda_du).sum();
+
+    let total = lambda_s + lambda_dd + lambda_du;
+    let sff = (total > 0.0).then(|| (lambda_s + lambda_dd) / total);
+    let max_sil = sff.and_then(|sff| route_1h(element_type, sff, hft));
+
+    SilCapability {
+        element_type,
+        hft,
+        lambda_s,
+        lambda_dd,
+        lambda_du,
+        sff,
+        max_sil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// IEC 61508-2 Tables 2 and 3, rows SFF < 60 %, 60–90 %, 90–99 %, ≥ 99 %; columns HFT 0, 1, 2
+    #[test]
+    fn route_1h_tables() {
+        let sffs = [0.5, 0.75, 0.95, 0.995];
+        let type_a = [[1, 2, 3], [2, 3, 4], [3, 4, 4], [3, 4, 4]];
+        let type_b = [[0, 1, 2], [1, 2, 3], [2, 3, 4], [3, 4, 4]];
+
+        for (row, sff) in sffs.iter().enumerate() {
+            for hft in 0..3 {
+                let level = |sil: Option<Sil>| sil.map_or(0, |s| s as u8);
+                assert_eq!(level(route_1h(ElementType::A, *sff, hft)), type_a[row][hft as usize]);
+                assert_eq!(level(route_1h(ElementType::B, *sff, hft)), type_b[row][hft as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn route_1h_boundaries_and_hft_cap() {
+        // Band limits belong to the higher band
+        assert_eq!(route_1h(ElementType::B, 0.60, 0), Some(Sil::Sil1));
+        assert_eq!(route_1h(ElementType::B, 0.90, 0), Some(Sil::Sil2));
+        assert_eq!(route_1h(ElementType::B, 0.99, 0), Some(Sil::Sil3));
+        assert_eq!(route_1h(ElementType::B, 0.5999, 0), None);
+        assert_eq!(route_1h(ElementType::A, 0.5, 5), route_1h(ElementType::A, 0.5, 2));
+    }
+
+    #[test]
+    fn sff_from_lambda_splits() {
+        let split = |lambda_s, lambda_dd, lambda_du| LambdaSplit {
+            failure_mode_id: Uuid::nil(),
+            mode: String::new(),
+            lambda_s,
+            lambda_dd,
+            lambda_du,
+        };
+        // (40 + 45) / 100 = 85 % → Type B, HFT 0: SIL 1
+        let capability = sil_capability(ElementType::B, 0, &[split(30.0, 40.0, 5.0), split(10.0, 5.0, 10.0)]);
+        assert!((capability.sff.unwrap() - 0.85).abs() < 1e-12);
+        assert_eq!(capability.max_sil, Some(Sil::Sil1));
+
+        let empty = sil_capability(ElementType::A, 1, &[]);
+        assert_eq!(empty.sff, None);
+        assert_eq!(empty.max_sil, None);
+    }
+}
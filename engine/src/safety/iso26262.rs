@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::FmedaError;
+use crate::models::{FMEDAPredictionResult, FailureMode, Project};
+use super::Asil;
+
+/// ISO 26262-5 fault classes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultClass {
+    Safe,
+    SinglePoint,
+    Residual,
+    LatentMultiplePoint,
+    DetectedMultiplePoint,
+}
+
+/// FIT of one failure mode split over the ISO 26262-5 fault classes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FaultClassification {
+    pub failure_mode_id: Uuid,
+    pub mode: String,
+    pub fit: f64,
+    pub safe_fit: f64,
+    pub single_point_fit: f64,
+    pub residual_fit: f64,
+    pub latent_fit: f64,
+    pub detected_fit: f64,
+    /// Class holding the largest share of the FIT
+    pub primary_class: FaultClass,
+}
+
+/// Single-Point Fault Metric and Latent Fault Metric of a project
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HardwareMetrics {
+    pub asil: Asil,
+    pub total_fit: f64,
+    pub safe_fit: f64,
+    pub single_point_fit: f64,
+    pub residual_fit: f64,
+    pub latent_fit: f64,
+    pub detected_fit: f64,
+    /// 1 − Σ(λSPF + λRF) / Σλ; `None` without safety-related FIT
+    pub spfm: Option<f64>,
+    /// 1 − ΣλMPF,L / Σ(λ − λSPF − λRF); `None` without multiple-point FIT
+    pub lfm: Option<f64>,
+    pub spfm_target: Option<f64>,
+    pub lfm_target: Option<f64>,
+    pub spfm_pass: bool,
+    pub lfm_pass: bool,
+}
+
+impl HardwareMetrics {
+    pub fn pass(&self) -> bool {
+        self.spfm_pass && self.lfm_pass
+    }
+}
+
+/// ISO 26262-5 Tables 4 and 5: (SPFM, LFM) targets per ASIL
+pub fn metric_targets(asil: Asil) -> (Option<f64>, Option<f64>) {
+    match asil {
+        Asil::QM | Asil::A => (None, None),
+        Asil::B => (Some(0.90), Some(0.60)),
+        Asil::C => (Some(0.97), Some(0.80)),
+        Asil::D => (Some(0.99), Some(0.90)),
+    }
+}
+
+/// Split the FIT of a failure mode over the fault classes.
+///
+/// - `safe_fraction` of the FIT is safe.
+/// - If the mode can violate the safety goal, the dangerous part is
+///   single-point without coverage, otherwise residual (1 − DC) and
+///   detected multiple-point (DC), with DC = `detection_coverage`.
+/// - If it cannot violate the goal on its own, the dangerous part is a
+///   multiple-point fault: latent (1 − DC_L) or detected (DC_L), with
+///   DC_L = `latent_coverage`.
+pub fn classify(failure_mode: &FailureMode, fit: f64) -> FaultClassification {
+    let fraction = |v: Option<f32>| (v.unwrap_or(0.0) as f64).clamp(0.0, 1.0);
+
+    let safe_fit = fit * fraction(failure_mode.safe_fraction);
+    let dangerous = fit - safe_fit;
+
+    let (mut single_point_fit, mut residual_fit, mut latent_fit, mut detected_fit) = (0.0, 0.0, 0.0, 0.0);
+    if failure_mode.violates_goal {
+        let dc = fraction(failure_mode.detection_coverage);
+        if dc > 0.0 {
+            residual_fit = dangerous * (1.0 - dc);
+            detected_fit = dangerous * dc;
+        } else {
+            single_point_fit = dangerous;
+        }
+    } else {
+        let dc_latent = fraction(failure_mode.latent_coverage);
+        latent_fit = dangerous * (1.0 - dc_latent);
+        detected_fit = dangerous * dc_latent;
+    }
+
+    let primary_class = [
+        (FaultClass::Safe, safe_fit),
+        (FaultClass::SinglePoint, single_point_fit),
+        (FaultClass::Residual, residual_fit),
+        (FaultClass::LatentMultiplePoint, latent_fit),
+        (FaultClass::DetectedMultiplePoint, detected_fit),
+    ]
+    .into_iter()
+    .fold((FaultClass::Safe, f64::MIN), |best, c| if c.1 > best.1 { c } else { best })
+    .0;
+
+    FaultClassification {
+        failure_mode_id: failure_mode.id,
+        mode: failure_mode.mode.clone(),
+        fit,
+        safe_fit,
+        single_point_fit,
+        residual_fit,
+        latent_fit,
+        detected_fit,
+        primary_class,
+    }
+}
+
+/// Classify the failure-mode FITs of an FMEDA prediction
+pub fn classify_prediction(
+    prediction: &FMEDAPredictionResult,
+    failure_modes: &[FailureMode],
+) -> Vec<FaultClassification> {
+    prediction
+        .failure_modes
+        .iter()
+        .filter_map(|fm_fit| {
+            let fm = failure_modes.iter().find(|fm| fm.id == fm_fit.failure_mode_id)?;
+            Some(classify(fm, fm_fit.fit))
+        })
+        .collect()
+}
+
+/// SPFM and LFM over the classified failure modes, checked against the project ASIL
+pub fn hardware_metrics(
+    project: &Project,
+    classified: &[FaultClassification],
+) -> Result<HardwareMetrics, FmedaError> {
+    let asil = Asil::from_project(project.asil_level.as_deref())?;
+    Ok(hardware_metrics_for(asil, classified))
+}
+
+/// SPFM and LFM over the classified failure modes, checked against `asil`
+pub fn hardware_metrics_for(asil: Asil, classified: &[FaultClassification]) -> HardwareMetrics {
+    let sum = |f: fn(&FaultClassification) -> f64| classified.iter().map(f).sum::<f64>();
+
+    let total_fit = sum(|c| c.fit);
+    let safe_fit = sum(|c| c.safe_fit);
+    let single_point_fit = sum(|c| c.single_point_fit);
+    let residual_fit = sum(|c| c.residual_fit);
+    let latent_fit = sum(|c| c.latent_fit);
+    let detected_fit = sum(|c| c.detected_fit);
+
+    let spfm = (total_fit > 0.0).then(|| 1.0 - (single_point_fit + residual_fit) / total_fit);
+    let mpf_base = total_fit - single_point_fit - residual_fit;
+    let lfm = (mpf_base > 0.0).then(|| 1.0 - latent_fit / mpf_base);
+
+    let (spfm_target, lfm_target) = metric_targets(asil);
+    let meets = |value: Option<f64>, target: Option<f64>| match (value, target) {
+        (Some(v), Some(t)) => v >= t,
+        _ => true,
+    };
+
+    HardwareMetrics {
+        asil,
+        total_fit,
+        safe_fit,
+        single_point_fit,
+        residual_fit,
+        latent_fit,
+        detected_fit,
+        spfm,
+        lfm,
+        spfm_target,
+        lfm_target,
+        spfm_pass: meets(spfm, spfm_target),
+        lfm_pass: meets(lfm, lfm_target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::failure_mode;
+
+    fn close(actual: f64, expected: f64) -> bool {
+        // Fractions are stored as f32
+        (actual - expected).abs() <= 1e-6 * expected.abs().max(1.0)
+    }
+
+    fn mode(safe: f32, violates_goal: bool, dc: Option<f32>, latent: Option<f32>) -> FailureMode {
+        FailureMode {
+            safe_fraction: Some(safe),
+            violates_goal,
+            latent_coverage: latent,
+            ..failure_mode("test", 1.0, dc)
+        }
+    }
+
+    #[test]
+    fn classification_splits_the_fit() {
+        // 20 % safe; of the dangerous 80 FIT, DC = 99 % is detected
+        let covered = classify(&mode(0.2, true, Some(0.99), None), 100.0);
+        assert!(close(covered.safe_fit, 20.0));
+        assert!(close(covered.residual_fit, 0.8));
+        assert!(close(covered.detected_fit, 79.2));
+        assert_eq!(covered.single_point_fit, 0.0);
+        assert_eq!(covered.primary_class, FaultClass::DetectedMultiplePoint);
+
+        let uncovered = classify(&mode(0.0, true, None, None), 50.0);
+        assert!(close(uncovered.single_point_fit, 50.0));
+        assert_eq!(uncovered.primary_class, FaultClass::SinglePoint);
+
+        let multiple_point = classify(&mode(0.0, false, Some(0.99), Some(0.9)), 50.0);
+        assert!(close(multiple_point.latent_fit, 5.0));
+        assert!(close(multiple_point.detected_fit, 45.0));
+        assert_eq!(multiple_point.residual_fit, 0.0);
+    }
+
+    #[test]
+    fn spfm_and_lfm_against_asil_targets() {
+        let covered = classify(&mode(0.2, true, Some(0.99), None), 100.0);
+        let uncovered = classify(&mode(0.0, true, None, None), 50.0);
+        let multiple_point = classify(&mode(0.0, false, None, Some(0.9)), 50.0);
+
+        // SPFM = 1 − (50 + 0.8)/200, LFM = 1 − 5/(200 − 50.8)
+        let metrics = hardware_metrics_for(Asil::B, &[covered.clone(), uncovered, multiple_point.clone()]);
+        assert!(close(metrics.spfm.unwrap(), 0.746));
+        assert!(close(metrics.lfm.unwrap(), 1.0 - 5.0 / 149.2));
+        assert!(!metrics.spfm_pass);
+        assert!(metrics.lfm_pass);
+
+        // SPFM = 1 − 0.8/150 = 0.99467 meets ASIL D
+        let metrics = hardware_metrics_for(Asil::D, &[covered, multiple_point]);
+        assert!(close(metrics.spfm.unwrap(), 1.0 - 0.8 / 150.0));
+        assert!(metrics.pass());
+    }
+
+    #[test]
+    fn metrics_are_undefined_without_fit() {
+        let metrics = hardware_metrics_for(Asil::D, &[]);
+        assert_eq!(metrics.spfm, None);
+        assert_eq!(metrics.lfm, None);
+        assert_eq!(metric_targets(Asil::A), (None, None));
+        assert_eq!("asil-d".parse::<Asil>().unwrap(), Asil::D);
+    }
+}
@@ -0,0 +1,56 @@
+pub mod iec61508;
+pub mod iso26262;
+pub mod pfd;
+pub mod pmhf;
+pub mod rollup;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::FmedaError;
+
+/// ISO 26262 Automotive Safety Integrity Level
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Asil {
+    QM,
+    A,
+    B,
+    C,
+    D,
+}
+
+impl FromStr for Asil {
+    type Err = FmedaError;
+
+    /// Accepts "D", "ASIL D", "asil-d", "QM", ...
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = s.trim().to_uppercase().replace(['-', '_', ' '], "");
+        match normalised.trim_start_matches("ASIL") {
+            "QM" => Ok(Asil::QM),
+            "A" => Ok(Asil::A),
+            "B" => Ok(Asil::B),
+            "C" => Ok(Asil::C),
+            "D" => Ok(Asil::D),
+            _ => Err(FmedaError::InvalidAsil(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Asil {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Asil::QM => write!(f, "QM"),
+            other => write!(f, "ASIL {:?}", other),
+        }
+    }
+}
+
+impl Asil {
+    /// ASIL stored on a project row
+    pub fn from_project(asil_level: Option<&str>) -> Result<Self, FmedaError> {
+        asil_level
+            .ok_or_else(|| FmedaError::InvalidAsil("not set".to_string()))?
+            .parse()
+    }
+}
@@ -0,0 +1,330 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::errors::FmedaError;
+use super::iec61508::LambdaSplit;
+
+/// Failure rates of one channel (per hour)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default)]
+pub struct ChannelRates {
+    pub lambda_du: f64,
+    pub lambda_dd: f64,
+    /// Safe detected rate, only used by 1oo2D
+    pub lambda_sd: f64,
+}
+
+impl ChannelRates {
+    /// Channel rates from the λ split of its failure modes (FIT).
+    /// All safe failures are counted as detected for 1oo2D.
+    pub fn from_splits(splits: &[LambdaSplit]) -> Self {
+        Self {
+            lambda_du: splits.iter().map(|s| s.lambda_du).sum::<f64>() * FIT_TO_PER_HOUR,
+            lambda_dd: splits.iter().map(|s| s.lambda_dd).sum::<f64>() * FIT_TO_PER_HOUR,
+            lambda_sd: splits.iter().map(|s| s.lambda_s).sum::<f64>() * FIT_TO_PER_HOUR,
+        }
+    }
+
+    pub fn lambda_d(&self) -> f64 {
+        self.lambda_du + self.lambda_dd
+    }
+}
+
+/// Proof-test, repair and common-cause parameters
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct ProofTestParams {
+    /// Proof-test interval T1 (h)
+    pub proof_test_interval_h: f64,
+    /// Mean time to restoration MTTR (h), also used as mean repair time MRT
+    pub mttr_h: f64,
+    /// Common-cause factor of undetected failures β
+    pub beta: f64,
+    /// Common-cause factor of detected failures βD
+    pub beta_d: f64,
+    /// Diagnostic coverage K of the 1oo2D channel comparison
+    #[serde(default = "default_comparison_coverage")]
+    pub comparison_coverage: f64,
+}
+
+fn default_comparison_coverage() -> f64 {
+    0.98
+}
+
+impl Default for ProofTestParams {
+    fn default() -> Self {
+        Self {
+            proof_test_interval_h: 8760.0,
+            mttr_h: 8.0,
+            beta: 0.10,
+            beta_d: 0.05,
+            comparison_coverage: default_comparison_coverage(),
+        }
+    }
+}
+
+/// Voting architecture of the subsystem
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    OneOoOne,
+    OneOoTwo,
+    TwoOoTwo,
+    OneOoTwoD,
+    TwoOoThree,
+    /// M-out-of-N channels needed to perform the safety function
+    MooN { m: u32, n: u32 },
+}
+
+impl fmt::Display for Architecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Architecture::OneOoTwoD => write!(f, "1oo2D"),
+            other => {
+                let (m, n) = other.m_n();
+                write!(f, "{}oo{}", m, n)
+            }
+        }
+    }
+}
+
+impl Architecture {
+    /// (M, N) of the architecture
+    pub fn m_n(&self) -> (u32, u32) {
+        match *self {
+            Architecture::OneOoOne => (1, 1),
+            Architecture::OneOoTwo | Architecture::OneOoTwoD => (1, 2),
+            Architecture::TwoOoTwo => (2, 2),
+            Architecture::TwoOoThree => (2, 3),
+            Architecture::MooN { m, n } => (m, n),
+        }
+    }
+}
+
+/// Average probability of failure on demand and probability of dangerous failure per hour
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PfdPfhResult {
+    pub architecture: Architecture,
+    pub pfd_avg: f64,
+    /// PFH (1/h)
+    pub pfh: f64,
+    /// Channel equivalent mean down time tCE (h); tCE' for 1oo2D
+    pub t_ce: f64,
+    /// System equivalent mean down time tGE (h); tGE' for 1oo2D
+    pub t_ge: f64,
+}
+
+/// IEC 61508-6 Annex B simplified equations for PFDavg (low demand) and PFH (high demand).
+///
+/// A MooN system fails once k = N − M + 1 channels have failed dangerously:
+///
+/// PFDavg = N!/(N−k)!·λD'^k·tCE·tG1E·…·tG(k−1)E + βD·λDD·MTTR + β·λDU·(T1/2 + MRT)
+/// PFH    = N!/(N−k)!·λD'^(k−1)·(1−β)·λDU·tCE·tG1E·…·tG(k−2)E + β·λDU
+///
+/// with λD' = (1−βD)·λDD + (1−β)·λDU and the equivalent mean down times
+/// tGjE = λDU/λD·(T1/(j+2) + MRT) + λDD/λD·MTTR, so tCE = tG0E, tGE = tG1E (T1/3)
+/// and tG2E (T1/4) as in B.3.2.2; the common-cause terms only apply for k ≥ 2.
+///
+/// 1oo2D follows B.3.2.2.5 / B.3.3.2.5 with the safe detected rate and comparison coverage K:
+///
+/// PFDavg = 2(1−β)λDU·((1−β)λDU + (1−βD)λDD + λSD)·tCE'·tGE' + 2(1−K)λDD·tCE' + β·λDU·(T1/2 + MRT)
+/// PFH    = 2(1−β)λDU·((1−β)λDU + (1−βD)λDD + λSD)·tCE' + 2(1−K)λDD + β·λDU
+///
+/// with tCE' = (λDU·(T1/2 + MRT) + (λDD + λSD)·MTTR) / (λDU + λDD + λSD) and tGE' = T1/3 + MRT.
+pub fn pfd_pfh(
+    architecture: Architecture,
+    channel: ChannelRates,
+    params: ProofTestParams,
+) -> Result<PfdPfhResult, FmedaError> {
+    let (m, n) = architecture.m_n();
+    if m == 0 || m > n {
+        return Err(FmedaError::InvalidArchitecture(architecture.to_string()));
+    }
+    if params.proof_test_interval_h <= 0.0 || params.mttr_h < 0.0 {
+        return Err(FmedaError::Calculation(
+            "proof-test interval must be positive and MTTR non-negative".to_string(),
+        ));
+    }
+    for (name, value) in [
+        ("β", params.beta),
+        ("βD", params.beta_d),
+        ("K", params.comparison_coverage),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            return Err(FmedaError::Calculation(format!("{} must be in [0, 1], got {}", name, value)));
+        }
+    }
+
+    let ChannelRates { lambda_du, lambda_dd, lambda_sd } = channel;
+    let t1 = params.proof_test_interval_h;
+    let mrt = params.mttr_h;
+    let mttr = params.mttr_h;
+    let (beta, beta_d) = (params.beta, params.beta_d);
+
+    let lambda_d = channel.lambda_d();
+    // Equivalent mean down time tGjE of the j-th further channel failure (j = 0: tCE)
+    let t_g = |j: i32| {
+        if lambda_d > 0.0 {
+            lambda_du / lambda_d * (t1 / (j + 2) as f64 + mrt) + lambda_dd / lambda_d * mttr
+        } else {
+            0.0
+        }
+    };
+
+    let ccf_pfd = beta_d * lambda_dd * mttr + beta * lambda_du * (t1 / 2.0 + mrt);
+    let ccf_pfh = beta * lambda_du;
+    let lambda_d_ind = (1.0 - beta_d) * lambda_dd + (1.0 - beta) * lambda_du;
+
+    let (pfd_avg, pfh, t_ce, t_ge) = if architecture == Architecture::OneOoTwoD {
+        let lambda_total = lambda_du + lambda_dd + lambda_sd;
+        let t_ce_d = if lambda_total > 0.0 {
+            (lambda_du * (t1 / 2.0 + mrt) + (lambda_dd + lambda_sd) * mttr) / lambda_total
+        } else {
+            0.0
+        };
+        let t_ge_d = t1 / 3.0 + mrt;
+        let independent = 2.0 * (1.0 - beta) * lambda_du * (lambda_d_ind + lambda_sd);
+        let comparison = 2.0 * (1.0 - params.comparison_coverage) * lambda_dd;
+        (
+            independent * t_ce_d * t_ge_d + comparison * t_ce_d + beta * lambda_du * (t1 / 2.0 + mrt),
+            independent * t_ce_d + comparison + ccf_pfh,
+            t_ce_d,
+            t_ge_d,
+        )
+    } else {
+        let k = (n - m + 1) as i32;
+        // N!/(N−k)!
+        let coefficient: f64 = ((n - k as u32 + 1)..=n).map(f64::from).product();
+        let t_ce = t_g(0);
+        // tCE·tG1E·…·tG(j)E
+        let down_time = |j: i32| t_ce * (1..=j).map(t_g).product::<f64>();
+
+        let (pfd_avg, pfh) = if k == 1 {
+            (coefficient * lambda_d * t_ce, coefficient * lambda_du)
+        } else {
+            (
+                coefficient * lambda_d_ind.powi(k) * down_time(k - 1) + ccf_pfd,
+                coefficient * lambda_d_ind.powi(k - 1) * (1.0 - beta) * lambda_du * down_time(k - 2) + ccf_pfh,
+            )
+        };
+        (pfd_avg, pfh, t_ce, t_g(1))
+    };
+
+    report!(
+        "[IEC61508] {} | λDU = {:.3e} | λDD = {:.3e} | tCE = {:.3} h | tGE = {:.3} h → PFDavg = {:.3e}, PFH = {:.3e}",
+        architecture, lambda_du, lambda_dd, t_ce, t_ge, pfd_avg, pfh
+    );
+
+    Ok(PfdPfhResult { architecture, pfd_avg, pfh, t_ce, t_ge })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// IEC 61508-6 Table B.2 conditions: T1 = 4380 h, MTTR = MRT = 8 h, βD = β/2,
+    /// channel λ = 1e-7 /h with λD = λS = λ/2 (all safe failures detected for 1oo2D)
+    fn table_b2(dc: f64, beta: f64) -> (ChannelRates, ProofTestParams) {
+        let lambda_d = 0.5e-7;
+        let channel = ChannelRates {
+            lambda_du: lambda_d * (1.0 - dc),
+            lambda_dd: lambda_d * dc,
+            lambda_sd: 0.5e-7,
+        };
+        let params = ProofTestParams {
+            proof_test_interval_h: 4380.0,
+            mttr_h: 8.0,
+            beta,
+            beta_d: beta / 2.0,
+            comparison_coverage: 0.98,
+        };
+        (channel, params)
+    }
+
+    /// Table values are given to two significant digits
+    fn assert_table(actual: f64, expected: f64) {
+        assert!(
+            ((actual - expected) / expected).abs() < 0.05,
+            "expected {:e}, got {:e}",
+            expected,
+            actual
+        );
+    }
+
+    fn pfd(architecture: Architecture, dc: f64, beta: f64) -> f64 {
+        let (channel, params) = table_b2(dc, beta);
+        pfd_pfh(architecture, channel, params).unwrap().pfd_avg
+    }
+
+    #[test]
+    fn one_oo_one_matches_table_b2() {
+        assert_table(pfd(Architecture::OneOoOne, 0.0, 0.0), 1.1e-4);
+        assert_table(pfd(Architecture::OneOoOne, 0.6, 0.0), 4.4e-5);
+        assert_table(pfd(Architecture::OneOoOne, 0.9, 0.0), 1.1e-5);
+        assert_table(pfd(Architecture::OneOoOne, 0.99, 0.0), 1.5e-6);
+    }
+
+    #[test]
+    fn one_oo_two_matches_table_b2() {
+        assert_table(pfd(Architecture::OneOoTwo, 0.0, 0.02), 2.2e-6);
+        assert_table(pfd(Architecture::OneOoTwo, 0.0, 0.10), 1.1e-5);
+        assert_table(pfd(Architecture::OneOoTwo, 0.0, 0.20), 2.2e-5);
+        assert_table(pfd(Architecture::OneOoTwo, 0.6, 0.02), 8.8e-7);
+        assert_table(pfd(Architecture::OneOoTwo, 0.9, 0.10), 1.1e-6);
+        assert_table(pfd(Architecture::OneOoTwo, 0.99, 0.02), 2.6e-8);
+    }
+
+    #[test]
+    fn two_oo_three_matches_table_b2() {
+        assert_table(pfd(Architecture::TwoOoThree, 0.0, 0.02), 2.2e-6);
+        assert_table(pfd(Architecture::TwoOoThree, 0.6, 0.02), 8.9e-7);
+        assert_table(pfd(Architecture::TwoOoThree, 0.9, 0.10), 1.1e-6);
+        assert_table(pfd(Architecture::TwoOoThree, 0.99, 0.20), 2.6e-7);
+    }
+
+    #[test]
+    fn one_oo_two_d_matches_table_b2() {
+        assert_table(pfd(Architecture::OneOoTwoD, 0.6, 0.02), 1.4e-6);
+        assert_table(pfd(Architecture::OneOoTwoD, 0.9, 0.02), 4.3e-7);
+        assert_table(pfd(Architecture::OneOoTwoD, 0.9, 0.10), 1.3e-6);
+        assert_table(pfd(Architecture::OneOoTwoD, 0.99, 0.02), 6.0e-8);
+    }
+
+    #[test]
+    fn one_oo_two_d_includes_comparison_term() {
+        // β = 0 isolates 2(1−K)λDD·tCE' next to the independent term with tGE' = T1/3 + MRT
+        let (channel, params) = table_b2(0.9, 0.0);
+        let result = pfd_pfh(Architecture::OneOoTwoD, channel, ProofTestParams { beta_d: 0.0, ..params }).unwrap();
+        let t_ce = (0.5e-8 * 2198.0 + (4.5e-8 + 0.5e-7) * 8.0) / 1e-7;
+        let expected = 2.0 * 0.5e-8 * (0.5e-8 + 4.5e-8 + 0.5e-7) * t_ce * 1468.0 + 2.0 * 0.02 * 4.5e-8 * t_ce;
+        assert!((result.t_ge - 1468.0).abs() < 1e-9);
+        assert!(((result.pfd_avg - expected) / expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn one_oo_three_uses_tg2e() {
+        // Without CCF: PFDavg = 6·λD^3·tCE·tGE·tG2E, tG2E = T1/4 + MRT at DC = 0
+        let (channel, params) = table_b2(0.0, 0.0);
+        let result = pfd_pfh(Architecture::MooN { m: 1, n: 3 }, channel, params).unwrap();
+        let expected = 6.0 * 0.5e-7_f64.powi(3) * 2198.0 * 1468.0 * 1103.0;
+        assert!(((result.pfd_avg - expected) / expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pfh_equations() {
+        let (channel, params) = table_b2(0.0, 0.02);
+        let one = pfd_pfh(Architecture::OneOoOne, channel, params).unwrap();
+        assert!((one.pfh - 0.5e-7).abs() < 1e-20);
+        let two = pfd_pfh(Architecture::TwoOoTwo, channel, params).unwrap();
+        assert!((two.pfh - 1e-7).abs() < 1e-20);
+        // 2·λD'·(1−β)λDU·tCE + βλDU
+        let one_oo_two = pfd_pfh(Architecture::OneOoTwo, channel, params).unwrap();
+        let lambda = 0.98 * 0.5e-7;
+        let expected = 2.0 * lambda * lambda * 2198.0 + 0.02 * 0.5e-7;
+        assert!(((one_oo_two.pfh - expected) / expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_invalid_architecture() {
+        let (channel, params) = table_b2(0.0, 0.0);
+        assert!(pfd_pfh(Architecture::MooN { m: 3, n: 2 }, channel, params).is_err());
+    }
+}
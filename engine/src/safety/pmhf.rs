@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::calc::FIT_TO_PER_HOUR;
+use crate::models::Project;
+use super::iso26262::FaultClassification;
+use super::Asil;
+
+/// Default vehicle operating lifetime (h) when the project does not set one
+pub const DEFAULT_VEHICLE_LIFETIME_H: f64 = 10_000.0;
+
+/// Default multiple-point fault detection interval (h): one driving cycle
+pub const DEFAULT_MPF_DETECTION_INTERVAL_H: f64 = 1.0;
+
+/// Time parameters of the PMHF evaluation
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct PmhfParams {
+    /// Vehicle operating lifetime TL (h), exposure time of latent faults
+    pub vehicle_lifetime_h: f64,
+    /// Multiple-point fault detection interval Tind (h), exposure time of detected faults
+    pub mpf_detection_interval_h: f64,
+}
+
+impl Default for PmhfParams {
+    fn default() -> Self {
+        Self {
+            vehicle_lifetime_h: DEFAULT_VEHICLE_LIFETIME_H,
+            mpf_detection_interval_h: DEFAULT_MPF_DETECTION_INTERVAL_H,
+        }
+    }
+}
+
+impl PmhfParams {
+    /// Parameters stored on the project row, defaults for the missing ones
+    pub fn from_project(project: &Project) -> Self {
+        Self {
+            vehicle_lifetime_h: project.vehicle_lifetime_hours.unwrap_or(DEFAULT_VEHICLE_LIFETIME_H),
+            mpf_detection_interval_h: project
+                .mpf_detection_interval_hours
+                .unwrap_or(DEFAULT_MPF_DETECTION_INTERVAL_H),
+        }
+    }
+}
+
+/// Contribution of one failure mode to the PMHF
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PmhfContributor {
+    pub failure_mode_id: Uuid,
+    pub mode: String,
+    /// λSPF + λRF of the mode (FIT)
+    pub single_point_fit: f64,
+    /// Dual-point contribution of the mode's latent and detected MPF (FIT)
+    pub multiple_point_fit: f64,
+    pub total_fit: f64,
+    /// Share of the PMHF (0–1)
+    pub share: f64,
+}
+
+/// PMHF of one safety goal
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PmhfResult {
+    pub safety_goal: String,
+    pub asil: Asil,
+    pub params: PmhfParams,
+    /// Σ(λSPF + λRF) (FIT)
+    pub single_point_fit: f64,
+    /// Dual-point contribution of latent multiple-point faults (FIT)
+    pub latent_fit: f64,
+    /// Dual-point contribution of detected multiple-point faults (FIT)
+    pub detected_fit: f64,
+    /// PMHF (FIT)
+    pub pmhf: f64,
+    pub target: Option<f64>,
+    pub pass: bool,
+    /// Failure modes sorted by decreasing contribution
+    pub contributors: Vec<PmhfContributor>,
+}
+
+/// ISO 26262-5 Table 6: PMHF target per ASIL (FIT)
+pub fn pmhf_target(asil: Asil) -> Option<f64> {
+    match asil {
+        Asil::QM => None,
+        Asil::A => Some(1000.0),
+        Asil::B | Asil::C => Some(100.0),
+        Asil::D => Some(10.0),
+    }
+}
+
+/// PMHF of a safety goal from the classified failure modes of its elements.
+///
+/// PMHF = Σ(λSPF + λRF) + Σλ(MPF,L)·λ(D)·TL/2 + Σλ(MPF,DP)·λ(D)·Tind/2
+///
+/// where λ(D) is the dangerous (non-safe) rate of the goal, acting as the
+/// second fault of a dual-point failure, TL the vehicle lifetime and Tind
+/// the multiple-point fault detection interval.
+pub fn evaluate_pmhf(
+    safety_goal: &str,
+    asil: Asil,
+    classified: &[FaultClassification],
+    params: PmhfParams,
+) -> PmhfResult {
+    let dangerous_fit: f64 = classified.iter().map(|c| c.fit - c.safe_fit).sum();
+
+    // FIT × (FIT → 1/h) × h → FIT
+    let dual_point =
+        |fit: f64, exposure_h: f64| fit * dangerous_fit * FIT_TO_PER_HOUR * exposure_h / 2.0;
+
+    let mut contributors: Vec<PmhfContributor> = classified
+        .iter()
+        .map(|c| {
+            let single_point_fit = c.single_point_fit + c.residual_fit;
+            let multiple_point_fit = dual_point(c.latent_fit, params.vehicle_lifetime_h)
+                + dual_point(c.detected_fit, params.mpf_detection_interval_h);
+            PmhfContributor {
+                failure_mode_id: c.failure_mode_id,
+                mode: c.mode.clone(),
+                single_point_fit,
+                multiple_point_fit,
+                total_fit: single_point_fit + multiple_point_fit,
+                share: 0.0,
+            }
+        })
+        .collect();
+
+    let single_point_fit: f64 = contributors.iter().map(|c| c.single_point_fit).sum();
+    let latent_fit = dual_point(classified.iter().map(|c| c.latent_fit).sum(), params.vehicle_lifetime_h);
+    let detected_fit = dual_point(
+        classified.iter().map(|c| c.detected_fit).sum(),
+        params.mpf_detection_interval_h,
+    );
+    let pmhf = single_point_fit + latent_fit + detected_fit;
+
+    for c in &mut contributors {
+        c.share = if pmhf > 0.0 { c.total_fit / pmhf } else { 0.0 };
+    }
+    contributors.sort_by(|a, b| b.total_fit.total_cmp(&a.total_fit));
+
+    let target = pmhf_target(asil);
+    let pass = target.is_none_or(|t| pmhf < t);
+
+    report!(
+        "[PMHF] {} ({}) | SPF+RF = {:.6} | MPF,L = {:.6} | MPF,DP = {:.6} → PMHF = {:.6} FIT",
+        safety_goal, asil, single_point_fit, latent_fit, detected_fit, pmhf
+    );
+
+    PmhfResult {
+        safety_goal: safety_goal.to_string(),
+        asil,
+        params,
+        single_point_fit,
+        latent_fit,
+        detected_fit,
+        pmhf,
+        target,
+        pass,
+        contributors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::safety::iso26262::FaultClass;
+
+    fn classification(mode: &str, single_point_fit: f64, latent_fit: f64, detected_fit: f64) -> FaultClassification {
+        FaultClassification {
+            failure_mode_id: Uuid::new_v4(),
+            mode: mode.to_string(),
+            fit: single_point_fit + latent_fit + detected_fit,
+            safe_fit: 0.0,
+            single_point_fit,
+            residual_fit: 0.0,
+            latent_fit,
+            detected_fit,
+            primary_class: FaultClass::Safe,
+        }
+    }
+
+    #[test]
+    fn pmhf_adds_dual_point_contributions() {
+        // λD = 101 FIT; latent: 10·101e-9·10000/2, detected: 90·101e-9·1/2
+        let classified = [classification("spf", 1.0, 0.0, 0.0), classification("mpf", 0.0, 10.0, 90.0)];
+        let result = evaluate_pmhf("SG1", Asil::D, &classified, PmhfParams::default());
+
+        assert!((result.latent_fit - 5.05e-3).abs() < 1e-12);
+        assert!((result.detected_fit - 4.545e-6).abs() < 1e-15);
+        assert!((result.pmhf - 1.005054545).abs() < 1e-12);
+        assert!(result.pass);
+        assert_eq!(result.contributors[0].mode, "spf");
+        let shares: f64 = result.contributors.iter().map(|c| c.share).sum();
+        assert!((shares - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pmhf_target_per_asil() {
+        let classified = [classification("spf", 20.0, 0.0, 0.0)];
+        assert!(!evaluate_pmhf("SG1", Asil::D, &classified, PmhfParams::default()).pass);
+        assert!(evaluate_pmhf("SG1", Asil::B, &classified, PmhfParams::default()).pass);
+        assert_eq!(pmhf_target(Asil::QM), None);
+        assert_eq!(pmhf_target(Asil::C), Some(100.0));
+    }
+}
@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::FmedaError;
+use crate::models::{Component, FMEDAPredictionResult, FailureMode, GoalMapping, ProjectHierarchy};
+use super::iso26262::{classify, hardware_metrics_for, FaultClassification, HardwareMetrics};
+use super::pmhf::{evaluate_pmhf, PmhfParams, PmhfResult};
+use super::Asil;
+
+/// FMEDA prediction of one component of the project
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentFmeda<'a> {
+    pub component: &'a Component,
+    pub prediction: &'a FMEDAPredictionResult,
+}
+
+/// Metrics of one safety goal over every block mapped to it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GoalRollup {
+    pub safety_goal_id: Uuid,
+    pub name: String,
+    pub asil: Asil,
+    pub metrics: HardwareMetrics,
+    pub pmhf: PmhfResult,
+}
+
+/// Metrics of one hardware block (including nested blocks) for one goal
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BlockGoalMetrics {
+    pub safety_goal_id: Uuid,
+    pub metrics: HardwareMetrics,
+}
+
+/// FIT and per-goal metrics of one hardware block, including nested blocks
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BlockRollup {
+    pub hardware_block_id: Uuid,
+    pub name: String,
+    pub total_fit: f64,
+    pub goals: Vec<BlockGoalMetrics>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HierarchyRollup {
+    pub blocks: Vec<BlockRollup>,
+    pub goals: Vec<GoalRollup>,
+}
+
+/// Classified failure mode of a component in a block, for one goal
+struct GoalFault {
+    hardware_block_id: Uuid,
+    safety_goal_id: Uuid,
+    classification: FaultClassification,
+}
+
+/// Roll the component FMEDAs up per hardware block and per safety goal.
+///
+/// A failure-mode FIT counts towards a goal when the failure mode is mapped
+/// to the goal for the block of the component or one of its enclosing blocks;
+/// the nearest such mapping decides whether it violates the goal directly or
+/// only as a multiple-point fault.
+pub fn rollup(
+    hierarchy: &ProjectHierarchy,
+    components: &[ComponentFmeda],
+    failure_modes: &[FailureMode],
+    pmhf_params: PmhfParams,
+) -> Result<HierarchyRollup, FmedaError> {
+    let goal_faults = goal_faults(hierarchy, components, failure_modes);
+
+    let mut goals = Vec::with_capacity(hierarchy.safety_goals.len());
+    for goal in &hierarchy.safety_goals {
+        let asil: Asil = goal.asil_level.parse()?;
+        let classified: Vec<FaultClassification> = goal_faults
+            .iter()
+            .filter(|f| f.safety_goal_id == goal.id)
+            .map(|f| f.classification.clone())
+            .collect();
+
+        goals.push(GoalRollup {
+            safety_goal_id: goal.id,
+            name: goal.name.clone(),
+            asil,
+            metrics: hardware_metrics_for(asil, &classified),
+            pmhf: evaluate_pmhf(&goal.name, asil, &classified, pmhf_params),
+        });
+    }
+
+    let blocks = hierarchy
+        .hardware_blocks
+        .iter()
+        .map(|block| {
+            let subtree = hierarchy.block_subtree(block.id);
+
+            let total_fit = components
+                .iter()
+                .filter(|c| c.component.hardware_block_id.is_some_and(|id| subtree.contains(&id)))
+                .map(|c| c.prediction.total_fit)
+                .sum();
+
+            let block_goals = goals
+                .iter()
+                .filter_map(|goal| {
+                    let classified: Vec<FaultClassification> = goal_faults
+                        .iter()
+                        .filter(|f| f.safety_goal_id == goal.safety_goal_id && subtree.contains(&f.hardware_block_id))
+                        .map(|f| f.classification.clone())
+                        .collect();
+                    (!classified.is_empty()).then(|| BlockGoalMetrics {
+                        safety_goal_id: goal.safety_goal_id,
+                        metrics: hardware_metrics_for(goal.asil, &classified),
+                    })
+                })
+                .collect();
+
+            BlockRollup {
+                hardware_block_id: block.id,
+                name: block.name.clone(),
+                total_fit,
+                goals: block_goals,
+            }
+        })
+        .collect();
+
+    Ok(HierarchyRollup { blocks, goals })
+}
+
+/// Classify every mapped failure-mode FIT of the components against its goal
+fn goal_faults(
+    hierarchy: &ProjectHierarchy,
+    components: &[ComponentFmeda],
+    failure_modes: &[FailureMode],
+) -> Vec<GoalFault> {
+    let mut faults = Vec::new();
+    for c in components {
+        let Some(block_id) = c.component.hardware_block_id else {
+            continue;
+        };
+        let ancestry = hierarchy.block_ancestry(block_id);
+        for fm_fit in &c.prediction.failure_modes {
+            let Some(failure_mode) = failure_modes.iter().find(|fm| fm.id == fm_fit.failure_mode_id) else {
+                continue;
+            };
+            for mapping in goal_mappings(&hierarchy.goal_mappings, &ancestry, failure_mode.id) {
+                let mut failure_mode = failure_mode.clone();
+                failure_mode.violates_goal = mapping.violates_goal;
+                faults.push(GoalFault {
+                    hardware_block_id: block_id,
+                    safety_goal_id: mapping.safety_goal_id,
+                    classification: classify(&failure_mode, fm_fit.fit),
+                });
+            }
+        }
+    }
+    faults
+}
+
+/// Mappings of a failure mode along the block ancestry, at most one per goal:
+/// a mapping on a nested block overrides one on an enclosing block
+fn goal_mappings<'a>(mappings: &'a [GoalMapping], ancestry: &[Uuid], failure_mode_id: Uuid) -> Vec<&'a GoalMapping> {
+    let mut selected: Vec<&GoalMapping> = Vec::new();
+    for block_id in ancestry {
+        for mapping in mappings
+            .iter()
+            .filter(|m| m.hardware_block_id == *block_id && m.failure_mode_id == failure_mode_id)
+        {
+            if !selected.iter().any(|s| s.safety_goal_id == mapping.safety_goal_id) {
+                selected.push(mapping);
+            }
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::{FailureModeFit, HardwareBlock, SafetyGoal};
+    use crate::test_support::{component, failure_mode};
+
+    fn block(id: Uuid, parent_id: Option<Uuid>, name: &str) -> HardwareBlock {
+        HardwareBlock {
+            id,
+            system_id: Uuid::nil(),
+            parent_id,
+            name: name.to_string(),
+            description: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn goal(id: Uuid, asil_level: &str) -> SafetyGoal {
+        SafetyGoal {
+            id,
+            project_id: Uuid::nil(),
+            name: format!("SG {}", asil_level),
+            description: None,
+            asil_level: asil_level.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn mapping(hardware_block_id: Uuid, failure_mode: &FailureMode, safety_goal_id: Uuid, violates_goal: bool) -> GoalMapping {
+        GoalMapping { hardware_block_id, failure_mode_id: failure_mode.id, safety_goal_id, violates_goal }
+    }
+
+    fn prediction(component: &Component, failure_mode: &FailureMode, fit: f64) -> FMEDAPredictionResult {
+        FMEDAPredictionResult {
+            component_id: component.id,
+            standard: "TEST".to_string(),
+            weighted_pi_t: None,
+            segments: Vec::new(),
+            total_fit: fit,
+            failure_modes: vec![FailureModeFit {
+                failure_mode_id: failure_mode.id,
+                mode: failure_mode.mode.clone(),
+                share: 1.0,
+                fit,
+            }],
+            distribution: None,
+        }
+    }
+
+    /// ECU ⊃ MCU; one component in each block, one goal
+    struct Fixture {
+        ecu: Uuid,
+        mcu: Uuid,
+        sg: Uuid,
+        components: Vec<Component>,
+        failure_modes: Vec<FailureMode>,
+        predictions: Vec<FMEDAPredictionResult>,
+    }
+
+    fn fixture() -> Fixture {
+        let (ecu, mcu, sg) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut regulator = component("capacitor");
+        regulator.hardware_block_id = Some(ecu);
+        let mut core = component("ic");
+        core.hardware_block_id = Some(mcu);
+        // 10 FIT covered at 90 % in the ECU, 2 FIT uncovered in the MCU
+        let failure_modes = vec![failure_mode("short", 1.0, Some(0.9)), failure_mode("stuck", 1.0, None)];
+        let predictions = vec![
+            prediction(&regulator, &failure_modes[0], 10.0),
+            prediction(&core, &failure_modes[1], 2.0),
+        ];
+        Fixture { ecu, mcu, sg, components: vec![regulator, core], failure_modes, predictions }
+    }
+
+    fn run(f: &Fixture, goal_mappings: Vec<GoalMapping>) -> HierarchyRollup {
+        let hierarchy = ProjectHierarchy {
+            safety_goals: vec![goal(f.sg, "ASIL B")],
+            hardware_blocks: vec![block(f.ecu, None, "ECU"), block(f.mcu, Some(f.ecu), "MCU")],
+            goal_mappings,
+            ..Default::default()
+        };
+        let components: Vec<ComponentFmeda> = f
+            .components
+            .iter()
+            .zip(&f.predictions)
+            .map(|(component, prediction)| ComponentFmeda { component, prediction })
+            .collect();
+        rollup(&hierarchy, &components, &f.failure_modes, PmhfParams::default()).unwrap()
+    }
+
+    #[test]
+    fn goal_metrics_over_mapped_blocks() {
+        let f = fixture();
+        let result = run(
+            &f,
+            vec![
+                mapping(f.ecu, &f.failure_modes[0], f.sg, true),
+                mapping(f.mcu, &f.failure_modes[1], f.sg, true),
+            ],
+        );
+
+        // SPFM = 1 − (1 + 2)/12
+        let metrics = &result.goals[0].metrics;
+        assert_eq!(metrics.total_fit, 12.0);
+        assert!((metrics.spfm.unwrap() - 0.75).abs() < 1e-6);
+        assert!(!metrics.spfm_pass);
+
+        let ecu = result.blocks.iter().find(|b| b.hardware_block_id == f.ecu).unwrap();
+        let mcu = result.blocks.iter().find(|b| b.hardware_block_id == f.mcu).unwrap();
+        assert_eq!(ecu.total_fit, 12.0);
+        assert_eq!(mcu.total_fit, 2.0);
+        assert_eq!(mcu.goals[0].metrics.spfm, Some(0.0));
+    }
+
+    #[test]
+    fn mapping_on_an_enclosing_block_covers_nested_components() {
+        let f = fixture();
+        let result = run(
+            &f,
+            vec![
+                mapping(f.ecu, &f.failure_modes[0], f.sg, true),
+                mapping(f.ecu, &f.failure_modes[1], f.sg, true),
+            ],
+        );
+        assert_eq!(result.goals[0].metrics.total_fit, 12.0);
+        let mcu = result.blocks.iter().find(|b| b.hardware_block_id == f.mcu).unwrap();
+        assert_eq!(mcu.goals[0].metrics.single_point_fit, 2.0);
+    }
+
+    #[test]
+    fn nested_mapping_overrides_the_enclosing_one() {
+        let f = fixture();
+        let result = run(
+            &f,
+            vec![
+                mapping(f.ecu, &f.failure_modes[1], f.sg, true),
+                mapping(f.mcu, &f.failure_modes[1], f.sg, false),
+            ],
+        );
+        // Counted once, as a latent multiple-point fault
+        let metrics = &result.goals[0].metrics;
+        assert_eq!(metrics.total_fit, 2.0);
+        assert_eq!(metrics.single_point_fit, 0.0);
+        assert_eq!(metrics.latent_fit, 2.0);
+    }
+}
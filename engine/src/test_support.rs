@@ -0,0 +1,75 @@
+//! Fixtures shared by the unit tests
+
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::calc::{CalcContext, ReliabilityStandard};
+use crate::errors::FmedaError;
+use crate::models::{Component, ComponentVariant, FailureMode, MissionProfile};
+
+/// Component of the given type with no stress data
+pub fn component(component_type: &str) -> Component {
+    serde_json::from_value(json!({
+        "id": Uuid::new_v4(),
+        "project_id": Uuid::new_v4(),
+        "manufacturer_part_number": format!("TEST-{}", component_type),
+        "quantity": 1,
+        "created_at": "2024-01-01T00:00:00Z",
+        "component_type": component_type,
+    }))
+    .unwrap()
+}
+
+/// Variant with the given reference FIT
+pub fn variant(ref_fit: f64) -> ComponentVariant {
+    serde_json::from_value(json!({
+        "id": Uuid::new_v4(),
+        "subtype_id": Uuid::new_v4(),
+        "name": "test",
+        "ref_fit": ref_fit,
+        "created_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Mission profile from (temperature, τ) segments
+pub fn profile(segments: &[(f64, f64)]) -> MissionProfile {
+    let segments: Vec<_> = segments
+        .iter()
+        .map(|(temperature, tau)| json!({ "temperature": temperature, "tau": tau }))
+        .collect();
+    serde_json::from_value(json!({
+        "id": Uuid::new_v4(),
+        "name": "test",
+        "temp_tau_profile": { "version": 2, "segments": segments },
+        "created_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Failure mode with the given λ and diagnostic coverage
+pub fn failure_mode(mode: &str, lambda: f64, detection_coverage: Option<f32>) -> FailureMode {
+    serde_json::from_value(json!({
+        "id": Uuid::new_v4(),
+        "mode": mode,
+        "lambda": lambda,
+        "detection_coverage": detection_coverage,
+        "created_at": "2024-01-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Standard returning the variant's reference FIT, independent of the profile
+pub struct ReferenceFit;
+
+impl ReliabilityStandard for ReferenceFit {
+    fn name(&self) -> &str {
+        "REFERENCE"
+    }
+
+    fn calc_fit(&self, ctx: &CalcContext) -> Result<f64, FmedaError> {
+        ctx.variant
+            .map(|v| v.reference_fit())
+            .ok_or_else(|| FmedaError::MissingVariant(self.name().to_string()))
+    }
+}
@@ -0,0 +1,253 @@
+pub mod montecarlo;
+pub mod sensitivity;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::calc::{CalcContext, ReliabilityStandard};
+use crate::errors::FmedaError;
+use crate::models::{predict_fmeda, Component, ComponentVariant, FailureMode, MissionProfile};
+use crate::safety::iso26262::{classify_prediction, hardware_metrics_for};
+use crate::safety::pmhf::{evaluate_pmhf, PmhfParams};
+use crate::safety::Asil;
+
+/// Uncertainty distribution of an input around its point value
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Distribution {
+    /// Lognormal with the point value as median and error factor EF = x95 / x50
+    Lognormal { error_factor: f64 },
+    /// Uniform between absolute bounds
+    Uniform { low: f64, high: f64 },
+    /// Lognormal fitted to two-sided confidence bounds, e.g. 90 % → (x5, x95)
+    ConfidenceBounds { lower: f64, upper: f64, confidence: f64 },
+}
+
+/// z value of the 95th percentile of the standard normal distribution
+const Z_95: f64 = 1.6448536269514722;
+
+impl Distribution {
+    pub fn validate(&self) -> Result<(), FmedaError> {
+        let ok = match *self {
+            Distribution::Lognormal { error_factor } => error_factor.is_finite() && error_factor >= 1.0,
+            Distribution::Uniform { low, high } => low.is_finite() && high.is_finite() && low <= high,
+            Distribution::ConfidenceBounds { lower, upper, confidence } => {
+                upper.is_finite() && lower > 0.0 && lower <= upper && confidence > 0.0 && confidence < 1.0
+            }
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(FmedaError::InvalidDistribution(format!("{:?}", self)))
+        }
+    }
+
+    /// Draw one value; `point` is the nominal value of the input
+    pub fn sample<R: Rng + ?Sized>(&self, point: f64, rng: &mut R) -> f64 {
+        match *self {
+            Distribution::Lognormal { error_factor } => {
+                let sigma = error_factor.ln() / Z_95;
+                point * (sigma * standard_normal(rng)).exp()
+            }
+            Distribution::Uniform { low, high } => {
+                if high > low {
+                    rng.gen_range(low..high)
+                } else {
+                    low
+                }
+            }
+            Distribution::ConfidenceBounds { lower, upper, confidence } => {
+                let z = normal_quantile(0.5 + confidence / 2.0);
+                let median = (lower * upper).sqrt();
+                let sigma = (upper / lower).ln() / (2.0 * z);
+                median * (sigma * standard_normal(rng)).exp()
+            }
+        }
+    }
+}
+
+/// Standard normal deviate (Box–Muller)
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Quantile of the standard normal distribution (Acklam's rational approximation)
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p <= 0.0 {
+        f64::NEG_INFINITY
+    } else if p >= 1.0 {
+        f64::INFINITY
+    } else if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Summary statistics of a sampled output
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct Percentiles {
+    pub mean: f64,
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+impl Percentiles {
+    /// Statistics of the samples; `None` when there are none
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let at = |p: f64| {
+            let rank = p * (sorted.len() - 1) as f64;
+            let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+            sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+        };
+        Some(Self {
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            p5: at(0.05),
+            p50: at(0.50),
+            p95: at(0.95),
+        })
+    }
+}
+
+/// Inputs of one component for a single evaluation of the model
+pub(crate) struct ComponentInputs<'a> {
+    pub component: &'a Component,
+    pub variant: Option<ComponentVariant>,
+    pub failure_modes: Vec<FailureMode>,
+    pub ea_scale: f64,
+}
+
+/// Outputs of a single evaluation of the model
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Outputs {
+    pub total_fit: f64,
+    pub spfm: Option<f64>,
+    pub lfm: Option<f64>,
+    pub pmhf: f64,
+}
+
+/// Total FIT of the components under the standard and the ISO 26262 metrics over their failure modes.
+/// Runs without report lines: it is called once per sample.
+pub(crate) fn evaluate(
+    standard: &dyn ReliabilityStandard,
+    inputs: &[ComponentInputs],
+    profile: &MissionProfile,
+    asil: Asil,
+    pmhf: PmhfParams,
+) -> Result<Outputs, FmedaError> {
+    crate::report::quietly(|| {
+        let mut classified = Vec::new();
+        let mut total_fit = 0.0;
+
+        for c in inputs {
+            let ctx = CalcContext::new(c.component, profile, c.variant.as_ref()).with_ea_scale(c.ea_scale);
+            let prediction = predict_fmeda(standard, &ctx, &c.failure_modes)?;
+            total_fit += prediction.total_fit;
+            classified.extend(classify_prediction(&prediction, &c.failure_modes));
+        }
+
+        let metrics = hardware_metrics_for(asil, &classified);
+        let pmhf = evaluate_pmhf("uncertainty", asil, &classified, pmhf);
+
+        Ok(Outputs {
+            total_fit,
+            spfm: metrics.spfm,
+            lfm: metrics.lfm,
+            pmhf: pmhf.pmhf,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn uniform_bounds_must_be_finite_and_ordered() {
+        assert!(Distribution::Uniform { low: 1.0, high: 2.0 }.validate().is_ok());
+        assert!(Distribution::Uniform { low: 2.0, high: 1.0 }.validate().is_err());
+        assert!(Distribution::Uniform { low: f64::NAN, high: 1.0 }.validate().is_err());
+        assert!(Distribution::Uniform { low: 0.0, high: f64::INFINITY }.validate().is_err());
+        assert!(Distribution::Lognormal { error_factor: f64::INFINITY }.validate().is_err());
+        assert!(Distribution::ConfidenceBounds { lower: 1.0, upper: f64::INFINITY, confidence: 0.9 }
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn normal_quantiles() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.95) - Z_95).abs() < 1e-8);
+        assert!((normal_quantile(0.01) + 2.326347874).abs() < 1e-8);
+        assert!((normal_quantile(0.999) - 3.090232306).abs() < 1e-8);
+    }
+
+    #[test]
+    fn lognormal_error_factor_sets_p95() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let dist = Distribution::Lognormal { error_factor: 3.0 };
+        let samples: Vec<f64> = (0..20_000).map(|_| dist.sample(10.0, &mut rng)).collect();
+        let stats = Percentiles::from_samples(&samples).unwrap();
+        assert!((stats.p50 - 10.0).abs() < 0.3);
+        assert!((stats.p95 - 30.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn percentiles_interpolate() {
+        let samples: Vec<f64> = (0..=100).map(f64::from).collect();
+        let stats = Percentiles::from_samples(&samples).unwrap();
+        assert_eq!((stats.p5, stats.p50, stats.p95, stats.mean), (5.0, 50.0, 95.0, 50.0));
+        assert!(Percentiles::from_samples(&[]).is_none());
+    }
+}
@@ -0,0 +1,190 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::calc::ReliabilityStandard;
+use crate::errors::FmedaError;
+use crate::models::{Component, ComponentVariant, FailureMode, MissionProfile};
+use crate::safety::pmhf::PmhfParams;
+use crate::safety::Asil;
+use super::{evaluate, ComponentInputs, Distribution, Percentiles};
+
+/// A component with the uncertainty of its inputs
+#[derive(Debug, Clone)]
+pub struct UncertainComponent<'a> {
+    pub component: &'a Component,
+    pub variant: Option<&'a ComponentVariant>,
+    pub failure_modes: &'a [FailureMode],
+    /// Uncertainty of the variant reference FIT, around the field FIT when one is recorded
+    pub ref_fit: Option<Distribution>,
+    /// Uncertainty of the activation energies, as a multiplier of the handbook values (point = 1)
+    pub activation_energy: Option<Distribution>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MonteCarloConfig {
+    pub iterations: usize,
+    /// Seed for reproducible runs
+    pub seed: Option<u64>,
+    pub asil: Asil,
+    #[serde(default)]
+    pub pmhf: PmhfParams,
+    /// Uncertainty of the detection coverage per failure mode
+    #[serde(default)]
+    pub detection_coverage: HashMap<Uuid, Distribution>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MonteCarloResult {
+    pub iterations: usize,
+    pub total_fit: Option<Percentiles>,
+    pub spfm: Option<Percentiles>,
+    pub lfm: Option<Percentiles>,
+    pub pmhf: Option<Percentiles>,
+}
+
+/// Propagate the input uncertainties through the FIT calculation of the
+/// standard and the ISO 26262 metrics by Monte Carlo sampling
+pub fn run(
+    standard: &dyn ReliabilityStandard,
+    components: &[UncertainComponent],
+    profile: &MissionProfile,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloResult, FmedaError> {
+    if config.iterations == 0 {
+        return Err(FmedaError::Calculation("Monte Carlo needs at least one iteration".to_string()));
+    }
+    profile.validate()?;
+    for c in components {
+        c.ref_fit.iter().chain(&c.activation_energy).try_for_each(Distribution::validate)?;
+    }
+    config.detection_coverage.values().try_for_each(Distribution::validate)?;
+
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut total_fits = Vec::with_capacity(config.iterations);
+    let mut spfms = Vec::with_capacity(config.iterations);
+    let mut lfms = Vec::with_capacity(config.iterations);
+    let mut pmhfs = Vec::with_capacity(config.iterations);
+
+    for _ in 0..config.iterations {
+        let inputs: Vec<ComponentInputs> = components
+            .iter()
+            .map(|c| {
+                let variant = match (c.variant, c.ref_fit) {
+                    (Some(v), Some(dist)) => Some(ComponentVariant {
+                        ref_fit: dist.sample(v.reference_fit(), &mut rng).max(0.0),
+                        field_fit: None,
+                        ..v.clone()
+                    }),
+                    (v, _) => v.cloned(),
+                };
+                let ea_scale = c.activation_energy.map_or(1.0, |d| d.sample(1.0, &mut rng).max(0.0));
+                let failure_modes = c
+                    .failure_modes
+                    .iter()
+                    .map(|fm| match config.detection_coverage.get(&fm.id) {
+                        Some(dist) => {
+                            let point = fm.detection_coverage.unwrap_or(0.0) as f64;
+                            FailureMode {
+                                detection_coverage: Some(dist.sample(point, &mut rng).clamp(0.0, 1.0) as f32),
+                                ..fm.clone()
+                            }
+                        }
+                        None => fm.clone(),
+                    })
+                    .collect();
+
+                ComponentInputs { component: c.component, variant, failure_modes, ea_scale }
+            })
+            .collect();
+
+        let outputs = evaluate(standard, &inputs, profile, config.asil, config.pmhf)?;
+        total_fits.push(outputs.total_fit);
+        spfms.extend(outputs.spfm);
+        lfms.extend(outputs.lfm);
+        pmhfs.push(outputs.pmhf);
+    }
+
+    let result = MonteCarloResult {
+        iterations: config.iterations,
+        total_fit: Percentiles::from_samples(&total_fits),
+        spfm: Percentiles::from_samples(&spfms),
+        lfm: Percentiles::from_samples(&lfms),
+        pmhf: Percentiles::from_samples(&pmhfs),
+    };
+
+    if let Some(fit) = result.total_fit {
+        report!(
+            "[MonteCarlo] {} iterations | FIT p5 = {:.6} | p50 = {:.6} | p95 = {:.6}",
+            config.iterations, fit.p5, fit.p50, fit.p95
+        );
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{component, failure_mode, profile, variant, ReferenceFit};
+
+    fn config(iterations: usize) -> MonteCarloConfig {
+        MonteCarloConfig {
+            iterations,
+            seed: Some(42),
+            asil: Asil::B,
+            pmhf: PmhfParams::default(),
+            detection_coverage: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn reference_fit_uncertainty_propagates() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let modes = [failure_mode("open", 1.0, Some(0.9))];
+        let components = [UncertainComponent {
+            component: &comp,
+            variant: Some(&var),
+            failure_modes: &modes,
+            ref_fit: Some(Distribution::Lognormal { error_factor: 3.0 }),
+            activation_energy: None,
+        }];
+
+        let result = run(&ReferenceFit, &components, &prof, &config(5_000)).unwrap();
+        let fit = result.total_fit.unwrap();
+        assert!((fit.p50 - 10.0).abs() < 0.5);
+        assert!((fit.p95 - 30.0).abs() < 2.0);
+        // SPFM is fixed by the 90 % coverage whatever the FIT
+        let spfm = result.spfm.unwrap();
+        assert!((spfm.p5 - 0.9).abs() < 1e-6 && (spfm.p95 - 0.9).abs() < 1e-6);
+
+        // Same seed, same result
+        let again = run(&ReferenceFit, &components, &prof, &config(5_000)).unwrap();
+        assert_eq!(again.total_fit.unwrap().p50, fit.p50);
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let modes = [failure_mode("open", 1.0, None)];
+        let components = [UncertainComponent {
+            component: &comp,
+            variant: Some(&var),
+            failure_modes: &modes,
+            ref_fit: Some(Distribution::Uniform { low: f64::NEG_INFINITY, high: 1.0 }),
+            activation_energy: None,
+        }];
+
+        assert!(run(&ReferenceFit, &components, &prof, &config(0)).is_err());
+        assert!(matches!(
+            run(&ReferenceFit, &components, &prof, &config(10)),
+            Err(FmedaError::InvalidDistribution(_))
+        ));
+    }
+}
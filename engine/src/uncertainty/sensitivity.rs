@@ -0,0 +1,414 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::calc::ReliabilityStandard;
+use crate::errors::FmedaError;
+use crate::models::{Component, ComponentVariant, FailureMode, MissionProfile};
+use crate::safety::pmhf::PmhfParams;
+use crate::safety::Asil;
+use super::{evaluate, ComponentInputs, Outputs};
+
+/// Input varied by the sensitivity analysis
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Parameter {
+    /// Temperature (°C) of a mission-profile segment
+    SegmentTemperature { segment: usize },
+    /// τ share of a segment; the other segments are rescaled to keep Στ
+    TauShare { segment: usize },
+    /// Reference FIT of the variant of a component (index into the components);
+    /// replaces a recorded field FIT
+    RefFit { component: usize },
+    /// Detection coverage of a failure mode
+    DetectionCoverage { failure_mode_id: Uuid },
+}
+
+/// Output the sensitivity is measured on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Output {
+    TotalFit,
+    Spfm,
+    Lfm,
+    Pmhf,
+}
+
+/// Parameter with the range it is varied over
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParameterRange {
+    pub parameter: Parameter,
+    pub label: Option<String>,
+    pub low: f64,
+    pub high: f64,
+}
+
+/// A component of the model under analysis
+#[derive(Debug, Clone, Copy)]
+pub struct SensitivityComponent<'a> {
+    pub component: &'a Component,
+    pub variant: Option<&'a ComponentVariant>,
+    pub failure_modes: &'a [FailureMode],
+}
+
+/// Model evaluated by the sensitivity analysis
+pub struct SensitivityModel<'a> {
+    pub standard: &'a dyn ReliabilityStandard,
+    pub components: &'a [SensitivityComponent<'a>],
+    pub profile: &'a MissionProfile,
+    pub asil: Asil,
+    pub pmhf: PmhfParams,
+    pub output: Output,
+}
+
+/// Bar of a tornado chart
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TornadoBar {
+    pub label: String,
+    pub parameter: Parameter,
+    pub low: f64,
+    pub high: f64,
+    pub output_low: f64,
+    pub output_high: f64,
+    /// |output_high − output_low|
+    pub swing: f64,
+}
+
+/// One-at-a-time sensitivity, bars sorted by decreasing swing
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TornadoData {
+    pub output: Output,
+    pub baseline: f64,
+    pub bars: Vec<TornadoBar>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SobolIndex {
+    pub label: String,
+    pub parameter: Parameter,
+    /// First-order index Si: share of the variance explained by the parameter alone
+    pub first_order: f64,
+    /// Total-effect index STi: share including all interactions
+    pub total: f64,
+}
+
+/// Variance-based sensitivity, indices sorted by decreasing total effect
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SobolResult {
+    pub output: Output,
+    pub samples: usize,
+    pub variance: f64,
+    pub indices: Vec<SobolIndex>,
+}
+
+impl ParameterRange {
+    fn label(&self) -> String {
+        self.label.clone().unwrap_or_else(|| match self.parameter {
+            Parameter::SegmentTemperature { segment } => format!("Segment {} temperature", segment + 1),
+            Parameter::TauShare { segment } => format!("Segment {} τ share", segment + 1),
+            Parameter::RefFit { component } => format!("Component {} reference FIT", component + 1),
+            Parameter::DetectionCoverage { failure_mode_id } => format!("DC of failure mode {}", failure_mode_id),
+        })
+    }
+}
+
+impl SensitivityModel<'_> {
+    fn validate(&self, ranges: &[ParameterRange]) -> Result<(), FmedaError> {
+        let segments = self.profile.segments().len();
+        for range in ranges {
+            let valid = range.low <= range.high
+                && match range.parameter {
+                    Parameter::SegmentTemperature { segment } => segment < segments,
+                    Parameter::TauShare { segment } => {
+                        // The other segments take up the rest of Στ, so there must be at least one
+                        segment < segments && segments > 1 && range.low >= 0.0 && range.high <= 1.0
+                    }
+                    Parameter::RefFit { component } => {
+                        self.components.get(component).is_some_and(|c| c.variant.is_some()) && range.low >= 0.0
+                    }
+                    Parameter::DetectionCoverage { failure_mode_id } => {
+                        self.components
+                            .iter()
+                            .flat_map(|c| c.failure_modes)
+                            .any(|fm| fm.id == failure_mode_id)
+                            && range.low >= 0.0
+                            && range.high <= 1.0
+                    }
+                };
+            if !valid {
+                return Err(FmedaError::InvalidSensitivityParameter(range.label()));
+            }
+        }
+        self.profile.validate()
+    }
+
+    /// Output of the model with the parameters set to `values`
+    fn run(&self, ranges: &[ParameterRange], values: &[(usize, f64)]) -> Result<f64, FmedaError> {
+        let mut profile = self.profile.clone();
+        let mut inputs: Vec<ComponentInputs> = self
+            .components
+            .iter()
+            .map(|c| ComponentInputs {
+                component: c.component,
+                variant: c.variant.cloned(),
+                failure_modes: c.failure_modes.to_vec(),
+                ea_scale: 1.0,
+            })
+            .collect();
+
+        for &(index, value) in values {
+            match ranges[index].parameter {
+                Parameter::SegmentTemperature { segment } => {
+                    profile.temp_tau_profile.segments[segment].temperature = value;
+                }
+                Parameter::TauShare { segment } => set_tau_share(&mut profile, segment, value),
+                Parameter::RefFit { component } => {
+                    if let Some(variant) = inputs[component].variant.as_mut() {
+                        variant.ref_fit = value;
+                        variant.field_fit = None;
+                    }
+                }
+                Parameter::DetectionCoverage { failure_mode_id } => {
+                    for fm in inputs.iter_mut().flat_map(|c| c.failure_modes.iter_mut()) {
+                        if fm.id == failure_mode_id {
+                            fm.detection_coverage = Some(value as f32);
+                        }
+                    }
+                }
+            }
+        }
+
+        let outputs = evaluate(self.standard, &inputs, &profile, self.asil, self.pmhf)?;
+        select(self.output, &outputs)
+    }
+}
+
+/// The analysed output; SPFM and LFM are undefined without safety-related
+/// (resp. latent-relevant) failure rate, which is an error rather than a NaN sample
+fn select(output: Output, outputs: &Outputs) -> Result<f64, FmedaError> {
+    let undefined = || FmedaError::Calculation(format!("{:?} is undefined for a sample of the model", output));
+    match output {
+        Output::TotalFit => Ok(outputs.total_fit),
+        Output::Spfm => outputs.spfm.ok_or_else(undefined),
+        Output::Lfm => outputs.lfm.ok_or_else(undefined),
+        Output::Pmhf => Ok(outputs.pmhf),
+    }
+}
+
+/// Set the τ share of a segment, rescaling the others so that Στ is unchanged.
+/// When the other segments have no τ, the remainder is split evenly over them.
+fn set_tau_share(profile: &mut MissionProfile, segment: usize, share: f64) {
+    let segments = &mut profile.temp_tau_profile.segments;
+    let total: f64 = segments.iter().map(|s| s.tau).sum();
+    let others: f64 = total - segments[segment].tau;
+    let remaining = total * (1.0 - share);
+    let other_count = (segments.len() - 1) as f64;
+
+    for (i, s) in segments.iter_mut().enumerate() {
+        if i == segment {
+            s.tau = total * share;
+        } else if others > 0.0 {
+            s.tau *= remaining / others;
+        } else {
+            s.tau = remaining / other_count;
+        }
+    }
+}
+
+/// One-at-a-time sensitivity: each parameter is set to its low and high
+/// value with all others at their nominal value
+pub fn one_at_a_time(model: &SensitivityModel, ranges: &[ParameterRange]) -> Result<TornadoData, FmedaError> {
+    model.validate(ranges)?;
+    let baseline = model.run(ranges, &[])?;
+
+    let mut bars = Vec::with_capacity(ranges.len());
+    for (index, range) in ranges.iter().enumerate() {
+        let output_low = model.run(ranges, &[(index, range.low)])?;
+        let output_high = model.run(ranges, &[(index, range.high)])?;
+        bars.push(TornadoBar {
+            label: range.label(),
+            parameter: range.parameter,
+            low: range.low,
+            high: range.high,
+            output_low,
+            output_high,
+            swing: (output_high - output_low).abs(),
+        });
+    }
+    bars.sort_by(|a, b| b.swing.total_cmp(&a.swing));
+
+    Ok(TornadoData { output: model.output, baseline, bars })
+}
+
+/// Sobol first-order and total-effect indices with the parameters uniform
+/// over their ranges, by the Saltelli (Si, on the centred output) and Jansen
+/// (STi) estimators.
+/// Costs `samples`·(k + 2) model evaluations for k parameters.
+pub fn sobol(
+    model: &SensitivityModel,
+    ranges: &[ParameterRange],
+    samples: usize,
+    seed: Option<u64>,
+) -> Result<SobolResult, FmedaError> {
+    model.validate(ranges)?;
+    if samples < 2 {
+        return Err(FmedaError::Calculation("Sobol analysis needs at least two samples".to_string()));
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let draw = |rng: &mut StdRng| -> Vec<f64> {
+        ranges
+            .iter()
+            .map(|r| if r.high > r.low { rng.gen_range(r.low..r.high) } else { r.low })
+            .collect()
+    };
+    let a: Vec<Vec<f64>> = (0..samples).map(|_| draw(&mut rng)).collect();
+    let b: Vec<Vec<f64>> = (0..samples).map(|_| draw(&mut rng)).collect();
+
+    let eval = |row: &[f64]| {
+        let values: Vec<(usize, f64)> = row.iter().copied().enumerate().collect();
+        model.run(ranges, &values)
+    };
+    let f_a: Vec<f64> = a.iter().map(|row| eval(row)).collect::<Result<_, _>>()?;
+    let f_b: Vec<f64> = b.iter().map(|row| eval(row)).collect::<Result<_, _>>()?;
+
+    let all: Vec<f64> = f_a.iter().chain(&f_b).copied().collect();
+    let mean = all.iter().sum::<f64>() / all.len() as f64;
+    let variance = all.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / (all.len() - 1) as f64;
+
+    let n = samples as f64;
+    let mut indices = Vec::with_capacity(ranges.len());
+    for (i, range) in ranges.iter().enumerate() {
+        // A with column i taken from B
+        let f_ab: Vec<f64> = a
+            .iter()
+            .zip(&b)
+            .map(|(row_a, row_b)| {
+                let mut row = row_a.clone();
+                row[i] = row_b[i];
+                eval(&row)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let (first_order, total) = if variance > 0.0 {
+            let s: f64 = (0..samples).map(|j| (f_b[j] - mean) * (f_ab[j] - f_a[j])).sum::<f64>() / n;
+            let st: f64 = (0..samples).map(|j| (f_a[j] - f_ab[j]).powi(2)).sum::<f64>() / (2.0 * n);
+            (s / variance, st / variance)
+        } else {
+            (0.0, 0.0)
+        };
+
+        indices.push(SobolIndex {
+            label: range.label(),
+            parameter: range.parameter,
+            first_order,
+            total,
+        });
+    }
+    indices.sort_by(|x, y| y.total.total_cmp(&x.total));
+
+    Ok(SobolResult { output: model.output, samples, variance, indices })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{component, failure_mode, profile, variant, ReferenceFit};
+
+    fn range(parameter: Parameter, low: f64, high: f64) -> ParameterRange {
+        ParameterRange { parameter, label: None, low, high }
+    }
+
+    #[test]
+    fn tau_share_keeps_the_tau_total() {
+        let mut prof = profile(&[(20.0, 0.5), (40.0, 0.3), (60.0, 0.2)]);
+        set_tau_share(&mut prof, 0, 0.8);
+        let taus: Vec<f64> = prof.segments().iter().map(|s| s.tau).collect();
+        assert!((taus[0] - 0.8).abs() < 1e-12);
+        assert!((taus[1] - 0.12).abs() < 1e-12 && (taus[2] - 0.08).abs() < 1e-12);
+
+        // All τ in the varied segment: the rest goes evenly to the others
+        let mut prof = profile(&[(20.0, 8760.0), (40.0, 0.0), (60.0, 0.0)]);
+        set_tau_share(&mut prof, 0, 0.5);
+        let taus: Vec<f64> = prof.segments().iter().map(|s| s.tau).collect();
+        assert_eq!(taus, vec![4380.0, 2190.0, 2190.0]);
+    }
+
+    #[test]
+    fn rejects_unknown_parameters() {
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let modes = [failure_mode("open", 1.0, Some(0.9))];
+        let components = [SensitivityComponent { component: &comp, variant: Some(&var), failure_modes: &modes }];
+        let model = SensitivityModel {
+            standard: &ReferenceFit,
+            components: &components,
+            profile: &prof,
+            asil: Asil::B,
+            pmhf: PmhfParams::default(),
+            output: Output::Spfm,
+        };
+
+        let unknown_fm = range(Parameter::DetectionCoverage { failure_mode_id: Uuid::new_v4() }, 0.5, 0.9);
+        assert!(matches!(one_at_a_time(&model, &[unknown_fm]), Err(FmedaError::InvalidSensitivityParameter(_))));
+        // A single segment has nothing to rescale
+        let tau = range(Parameter::TauShare { segment: 0 }, 0.2, 0.8);
+        assert!(one_at_a_time(&model, &[tau]).is_err());
+
+        let known_fm = range(Parameter::DetectionCoverage { failure_mode_id: modes[0].id }, 0.6, 0.99);
+        let tornado = one_at_a_time(&model, &[known_fm]).unwrap();
+        assert!((tornado.bars[0].output_low - 0.6).abs() < 1e-6);
+        assert!((tornado.bars[0].output_high - 0.99).abs() < 1e-6);
+    }
+
+    #[test]
+    fn undefined_metric_is_an_error() {
+        // No failure modes: no safety-related FIT, so SPFM is undefined
+        let (comp, var, prof) = (component("resistor"), variant(10.0), profile(&[(40.0, 1.0)]));
+        let components = [SensitivityComponent { component: &comp, variant: Some(&var), failure_modes: &[] }];
+        let model = SensitivityModel {
+            standard: &ReferenceFit,
+            components: &components,
+            profile: &prof,
+            asil: Asil::B,
+            pmhf: PmhfParams::default(),
+            output: Output::Spfm,
+        };
+        let ranges = [range(Parameter::RefFit { component: 0 }, 5.0, 20.0)];
+        assert!(matches!(one_at_a_time(&model, &ranges), Err(FmedaError::Calculation(_))));
+    }
+
+    #[test]
+    fn sobol_indices_of_an_additive_model() {
+        // FIT = x1 + x2 with x1 ~ U(0, 10), x2 ~ U(0, 20): S1 = 0.2, S2 = 0.8, no interactions
+        let prof = profile(&[(40.0, 1.0)]);
+        let (c1, c2) = (component("resistor"), component("capacitor"));
+        let (v1, v2) = (variant(5.0), variant(10.0));
+        let components = [
+            SensitivityComponent { component: &c1, variant: Some(&v1), failure_modes: &[] },
+            SensitivityComponent { component: &c2, variant: Some(&v2), failure_modes: &[] },
+        ];
+        let model = SensitivityModel {
+            standard: &ReferenceFit,
+            components: &components,
+            profile: &prof,
+            asil: Asil::B,
+            pmhf: PmhfParams::default(),
+            output: Output::TotalFit,
+        };
+        let ranges = [
+            range(Parameter::RefFit { component: 0 }, 0.0, 10.0),
+            range(Parameter::RefFit { component: 1 }, 0.0, 20.0),
+        ];
+
+        let result = sobol(&model, &ranges, 4_000, Some(1)).unwrap();
+        let index = |component| {
+            result.indices.iter().find(|i| i.parameter == Parameter::RefFit { component }).unwrap()
+        };
+        assert!((index(0).first_order - 0.2).abs() < 0.05 && (index(0).total - 0.2).abs() < 0.05);
+        assert!((index(1).first_order - 0.8).abs() < 0.05 && (index(1).total - 0.8).abs() < 0.05);
+    }
+}